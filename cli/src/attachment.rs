@@ -0,0 +1,296 @@
+use crate::create_confluence_client;
+use crate::output::{human_size, print_json, OutputFormat};
+use clap::{Args, Subcommand};
+use glob::Pattern;
+use nix_rust_template::{Attachment, ConfluencePage};
+use serde::Serialize;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+/// attachment command: list, upload and download attachments on pages
+/// matched by a CQL expression.
+///
+/// Intended usage examples:
+///   acli attachment ls "space = DOCS" --media-type image/
+///   acli attachment put "label = policy" ./policy.pdf --replace --existing-only
+///   acli attachment get "space = DOCS" --name "*.drawio" --out ./diagrams
+#[derive(Args, Debug)]
+pub struct AttachmentCmd {
+    #[command(subcommand)]
+    pub operation: AttachmentOp,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AttachmentOp {
+    /// List attachments on pages matching the CQL expression
+    Ls {
+        /// CQL expression selecting pages to operate on
+        cql: String,
+        #[command(flatten)]
+        filter: AttachmentFilter,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Upload files as attachments to pages matching the CQL expression
+    Put {
+        /// CQL expression selecting pages to operate on
+        cql: String,
+        /// Files to upload
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        /// Upload a new version when an attachment with the same name exists
+        #[arg(long)]
+        replace: bool,
+        /// Only upload to pages that already have an attachment with the same name
+        #[arg(long, requires = "replace")]
+        existing_only: bool,
+    },
+    /// Download attachments from pages matching the CQL expression
+    Get {
+        /// CQL expression selecting pages to operate on
+        cql: String,
+        #[command(flatten)]
+        filter: AttachmentFilter,
+        /// Directory to download into; files are stored under a folder per page ID
+        #[arg(short, long, default_value = ".")]
+        out: PathBuf,
+    },
+}
+
+/// Filters applied to attachments when listing or downloading.
+#[derive(Args, Debug)]
+pub struct AttachmentFilter {
+    /// Only include attachments whose media type starts with this (e.g. "image/")
+    #[arg(long)]
+    media_type: Option<String>,
+    /// Only include attachments whose file name matches this glob (e.g. "*.pdf")
+    #[arg(long)]
+    name: Option<String>,
+}
+
+/// An attachment together with the page it belongs to, for JSON output.
+#[derive(Debug, Serialize)]
+struct AttachmentRow<'a> {
+    page_id: &'a str,
+    page_title: &'a str,
+    id: &'a str,
+    title: &'a str,
+    media_type: Option<&'a str>,
+    size: Option<u64>,
+}
+
+/// Execute the attachment command.
+pub fn run(
+    cmd: &AttachmentCmd,
+    dry_run: bool,
+    pretty: bool,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+    let client = create_confluence_client()?;
+
+    match &cmd.operation {
+        AttachmentOp::Ls {
+            cql,
+            filter,
+            format,
+        } => {
+            if verbose {
+                eprintln!("Listing attachments on pages matching: {cql}");
+            }
+            let pattern = filter.pattern()?;
+            let pages = client.query_all_pages_by_cql(cql)?;
+
+            let mut listing: Vec<(ConfluencePage, Vec<Attachment>)> = Vec::new();
+            for page in pages {
+                let attachments: Vec<Attachment> = client
+                    .get_attachments(&page.id)?
+                    .into_iter()
+                    .filter(|a| filter.matches(a, pattern.as_ref()))
+                    .collect();
+                if !attachments.is_empty() {
+                    listing.push((page, attachments));
+                }
+            }
+
+            match format {
+                OutputFormat::Json => {
+                    let rows: Vec<AttachmentRow> = listing
+                        .iter()
+                        .flat_map(|(page, attachments)| {
+                            attachments.iter().map(move |a| AttachmentRow {
+                                page_id: &page.id,
+                                page_title: &page.title,
+                                id: &a.id,
+                                title: &a.title,
+                                media_type: a.media_type(),
+                                size: a.file_size(),
+                            })
+                        })
+                        .collect();
+                    print_json(&rows, pretty)?;
+                }
+                OutputFormat::Table => {
+                    if listing.is_empty() {
+                        println!("No attachments found.");
+                    }
+                    for (page, attachments) in &listing {
+                        println!("{} ({})", page.title, page.id);
+                        for attachment in attachments {
+                            println!(
+                                "  {}  {}  {}",
+                                attachment.title,
+                                attachment.media_type().unwrap_or("unknown"),
+                                attachment.file_size().map(human_size).unwrap_or_default()
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        AttachmentOp::Put {
+            cql,
+            files,
+            replace,
+            existing_only,
+        } => {
+            if verbose {
+                eprintln!("Uploading {files:?} to pages matching: {cql}");
+            }
+            for file in files {
+                if !file.is_file() {
+                    return Err(format!("Not a file: {}", file.display()).into());
+                }
+            }
+
+            let pages = client.query_all_pages_by_cql(cql)?;
+            if pages.is_empty() {
+                println!("No pages found matching CQL: {cql}");
+                return Ok(());
+            }
+
+            let mut uploaded = 0;
+            for page in &pages {
+                let existing: Vec<String> = if *replace {
+                    client
+                        .get_attachments(&page.id)?
+                        .into_iter()
+                        .map(|a| a.title)
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+
+                for file in files {
+                    let name = file
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or_default();
+                    let exists = existing.iter().any(|title| title == name);
+                    if *existing_only && !exists {
+                        if verbose {
+                            eprintln!("Skipping '{}': no existing {name}", page.title);
+                        }
+                        continue;
+                    }
+
+                    let action = if exists { "new version of " } else { "" };
+                    if dry_run {
+                        println!("DRY RUN: Would upload {action}{name} to '{}'", page.title);
+                    } else {
+                        client.upload_attachment(&page.id, file, *replace)?;
+                        println!("Uploaded {action}{name} to '{}'", page.title);
+                    }
+                    uploaded += 1;
+                }
+            }
+
+            let prefix = if dry_run {
+                "DRY RUN: Would upload"
+            } else {
+                "Uploaded"
+            };
+            println!(
+                "{prefix} {uploaded} attachments across {} pages",
+                pages.len()
+            );
+        }
+        AttachmentOp::Get { cql, filter, out } => {
+            if verbose {
+                eprintln!("Downloading attachments from pages matching: {cql}");
+            }
+            let pattern = filter.pattern()?;
+            let pages = client.query_all_pages_by_cql(cql)?;
+
+            let mut downloaded = 0;
+            let mut total_bytes = 0;
+            for page in &pages {
+                let attachments: Vec<Attachment> = client
+                    .get_attachments(&page.id)?
+                    .into_iter()
+                    .filter(|a| filter.matches(a, pattern.as_ref()))
+                    .collect();
+                if attachments.is_empty() {
+                    continue;
+                }
+
+                let page_dir = out.join(&page.id);
+                if !dry_run {
+                    fs::create_dir_all(&page_dir)?;
+                }
+
+                for attachment in &attachments {
+                    let dest = page_dir.join(attachment.title.replace(['/', '\\'], "_"));
+                    if dry_run {
+                        println!(
+                            "DRY RUN: Would download {} from '{}' to {}",
+                            attachment.title,
+                            page.title,
+                            dest.display()
+                        );
+                    } else {
+                        let bytes = client.download_attachment(attachment, &dest)?;
+                        total_bytes += bytes;
+                        println!(
+                            "Downloaded {} ({}) to {}",
+                            attachment.title,
+                            human_size(bytes),
+                            dest.display()
+                        );
+                    }
+                    downloaded += 1;
+                }
+            }
+
+            if dry_run {
+                println!("DRY RUN: Would download {downloaded} attachments");
+            } else {
+                println!(
+                    "Downloaded {downloaded} attachments ({})",
+                    human_size(total_bytes)
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+impl AttachmentFilter {
+    /// Compile the file name glob, if one was given.
+    fn pattern(&self) -> Result<Option<Pattern>, Box<dyn Error>> {
+        Ok(self.name.as_deref().map(Pattern::new).transpose()?)
+    }
+
+    /// Check whether an attachment passes the media type and name filters.
+    fn matches(&self, attachment: &Attachment, pattern: Option<&Pattern>) -> bool {
+        let media_type_ok = match &self.media_type {
+            Some(prefix) => attachment
+                .media_type()
+                .is_some_and(|m| m.starts_with(prefix.as_str())),
+            None => true,
+        };
+        let name_ok = pattern.is_none_or(|p| p.matches(&attachment.title));
+        media_type_ok && name_ok
+    }
+}
@@ -0,0 +1,337 @@
+use crate::create_confluence_client;
+use crate::output::{print_json, OutputFormat};
+use clap::{Args, Subcommand, ValueEnum};
+use nix_rust_template::{
+    markdown_to_storage, storage_to_markdown, Comment, CommentLocation, ConfluencePage, StorageBody,
+};
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+/// comment command: list, add and resolve comments on pages matched by a CQL
+/// expression.
+///
+/// Intended usage examples:
+///   acli comment ls "space = DOCS" --location inline --unresolved
+///   acli comment add "label = needs-review" --file review.md
+///   acli comment resolve 123456 123457
+///   acli comment report "space = DOCS or space = ENG"
+#[derive(Args, Debug)]
+pub struct CommentCmd {
+    #[command(subcommand)]
+    pub operation: CommentOp,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CommentOp {
+    /// List comments on pages matching the CQL expression
+    Ls {
+        /// CQL expression selecting pages to operate on
+        cql: String,
+        /// Which comments to include
+        #[arg(long, value_enum, default_value_t)]
+        location: LocationFilter,
+        /// Only include inline comments that are not resolved
+        #[arg(long)]
+        unresolved: bool,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Add a footer comment from a Markdown file to pages matching the CQL expression
+    Add {
+        /// CQL expression selecting pages to operate on
+        cql: String,
+        /// Markdown file holding the comment text
+        #[arg(long)]
+        file: PathBuf,
+    },
+    /// Resolve inline comments
+    Resolve {
+        /// IDs of the inline comments to resolve
+        #[arg(required = true)]
+        ids: Vec<String>,
+    },
+    /// Reopen resolved inline comments
+    Reopen {
+        /// IDs of the inline comments to reopen
+        #[arg(required = true)]
+        ids: Vec<String>,
+    },
+    /// Report unresolved inline comments per space for pages matching the CQL expression
+    Report {
+        /// CQL expression selecting pages to operate on
+        cql: String,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+}
+
+/// Comment locations to include when listing.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LocationFilter {
+    /// Footer and inline comments
+    #[default]
+    All,
+    /// Footer comments only
+    Footer,
+    /// Inline comments only
+    Inline,
+}
+
+/// A comment together with its page, for JSON output.
+#[derive(Debug, Serialize)]
+struct CommentRow<'a> {
+    page_id: &'a str,
+    page_title: &'a str,
+    id: &'a str,
+    location: &'static str,
+    author: &'a str,
+    date: Option<&'a str>,
+    resolution: Option<&'a str>,
+    body: String,
+}
+
+/// Unresolved comment counts for one space.
+#[derive(Debug, Default, Serialize)]
+struct SpaceReport {
+    space: String,
+    unresolved: usize,
+    pages: Vec<PageReport>,
+}
+
+/// Unresolved comment count for one page.
+#[derive(Debug, Serialize)]
+struct PageReport {
+    id: String,
+    title: String,
+    unresolved: usize,
+}
+
+/// Execute the comment command.
+pub fn run(
+    cmd: &CommentCmd,
+    dry_run: bool,
+    pretty: bool,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+    match &cmd.operation {
+        CommentOp::Ls {
+            cql,
+            location,
+            unresolved,
+            format,
+        } => {
+            if verbose {
+                eprintln!("Listing comments on pages matching: {cql}");
+            }
+            let client = create_confluence_client()?;
+            let locations = if *unresolved {
+                vec![CommentLocation::Inline]
+            } else {
+                location.locations()
+            };
+
+            let mut listing: Vec<(ConfluencePage, Vec<Comment>)> = Vec::new();
+            for page in client.query_all_pages_by_cql(cql)? {
+                let comments: Vec<Comment> = client
+                    .get_page_comments(&page.id, &locations)?
+                    .into_iter()
+                    .filter(|c| !*unresolved || !c.is_resolved())
+                    .collect();
+                if !comments.is_empty() {
+                    listing.push((page, comments));
+                }
+            }
+
+            match format {
+                OutputFormat::Json => {
+                    let rows: Vec<CommentRow> = listing
+                        .iter()
+                        .flat_map(|(page, comments)| {
+                            comments.iter().map(move |c| CommentRow {
+                                page_id: &page.id,
+                                page_title: &page.title,
+                                id: &c.id,
+                                location: c.location().as_str(),
+                                author: c.author(),
+                                date: c.date(),
+                                resolution: c.resolution(),
+                                body: storage_to_markdown(c.storage_body()),
+                            })
+                        })
+                        .collect();
+                    print_json(&rows, pretty)?;
+                }
+                OutputFormat::Table => {
+                    if listing.is_empty() {
+                        println!("No comments found.");
+                    }
+                    for (page, comments) in &listing {
+                        println!("{} ({})", page.title, page.id);
+                        for comment in comments {
+                            let state = match comment.resolution() {
+                                Some(resolution) => {
+                                    format!("{}, {resolution}", comment.location().as_str())
+                                }
+                                None => comment.location().as_str().to_string(),
+                            };
+                            println!(
+                                "  #{} [{state}] {} {}: {}",
+                                comment.id,
+                                comment.author(),
+                                comment.date().unwrap_or_default(),
+                                excerpt(comment.storage_body())
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        CommentOp::Add { cql, file } => {
+            if verbose {
+                eprintln!(
+                    "Adding comment from {} to pages matching: {cql}",
+                    file.display()
+                );
+            }
+            let markdown = fs::read_to_string(file)?;
+            let body = StorageBody::storage(markdown_to_storage(&markdown));
+
+            let client = create_confluence_client()?;
+            let pages = client.query_all_pages_by_cql(cql)?;
+            if pages.is_empty() {
+                println!("No pages found matching CQL: {cql}");
+                return Ok(());
+            }
+
+            for page in &pages {
+                if dry_run {
+                    println!("DRY RUN: Would add comment to '{}'", page.title);
+                } else {
+                    let comment = client.add_page_comment(&page.id, &body)?;
+                    println!("Added comment #{} to '{}'", comment.id, page.title);
+                }
+            }
+        }
+        CommentOp::Resolve { ids } => set_resolved(ids, true, dry_run, verbose)?,
+        CommentOp::Reopen { ids } => set_resolved(ids, false, dry_run, verbose)?,
+        CommentOp::Report { cql, format } => {
+            if verbose {
+                eprintln!("Reporting unresolved comments on pages matching: {cql}");
+            }
+            let client = create_confluence_client()?;
+
+            let mut spaces: BTreeMap<String, SpaceReport> = BTreeMap::new();
+            for page in client.query_all_pages_by_cql(cql)? {
+                let unresolved = client
+                    .get_page_comments(&page.id, &[CommentLocation::Inline])?
+                    .iter()
+                    .filter(|c| !c.is_resolved())
+                    .count();
+                if unresolved == 0 {
+                    continue;
+                }
+
+                let space = page
+                    .space
+                    .as_ref()
+                    .map_or_else(|| "unknown".to_string(), |s| s.key.clone());
+                let report = spaces.entry(space.clone()).or_insert_with(|| SpaceReport {
+                    space,
+                    ..SpaceReport::default()
+                });
+                report.unresolved += unresolved;
+                report.pages.push(PageReport {
+                    id: page.id,
+                    title: page.title,
+                    unresolved,
+                });
+            }
+
+            let mut reports: Vec<SpaceReport> = spaces.into_values().collect();
+            for report in &mut reports {
+                report.pages.sort_by_key(|p| Reverse(p.unresolved));
+            }
+
+            match format {
+                OutputFormat::Json => print_json(&reports, pretty)?,
+                OutputFormat::Table => {
+                    if reports.is_empty() {
+                        println!("No unresolved comments found.");
+                    }
+                    for report in &reports {
+                        println!(
+                            "{}: {} unresolved comments on {} pages",
+                            report.space,
+                            report.unresolved,
+                            report.pages.len()
+                        );
+                        for page in &report.pages {
+                            println!("  {:>4}  {} ({})", page.unresolved, page.title, page.id);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Resolve or reopen a list of inline comments.
+fn set_resolved(
+    ids: &[String],
+    resolved: bool,
+    dry_run: bool,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+    let (action, done) = if resolved {
+        ("resolve", "resolved")
+    } else {
+        ("reopen", "reopened")
+    };
+    if dry_run {
+        println!("DRY RUN: Would {action} comments {ids:?}");
+        return Ok(());
+    }
+
+    let client = create_confluence_client()?;
+    for id in ids {
+        if verbose {
+            eprintln!("Attempting to {action} comment {id}");
+        }
+        client.set_inline_comment_resolved(id, resolved)?;
+        println!("Comment #{id}: {done}");
+    }
+    Ok(())
+}
+
+/// First line of a comment rendered as Markdown, shortened for tables.
+fn excerpt(storage: &str) -> String {
+    const MAX_CHARS: usize = 80;
+    let markdown = storage_to_markdown(storage);
+    let line = markdown
+        .lines()
+        .find(|l| !l.trim().is_empty())
+        .unwrap_or("");
+    if line.chars().count() > MAX_CHARS {
+        format!("{}…", line.chars().take(MAX_CHARS).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+impl LocationFilter {
+    /// Comment locations selected by this filter.
+    fn locations(&self) -> Vec<CommentLocation> {
+        match self {
+            LocationFilter::All => vec![CommentLocation::Footer, CommentLocation::Inline],
+            LocationFilter::Footer => vec![CommentLocation::Footer],
+            LocationFilter::Inline => vec![CommentLocation::Inline],
+        }
+    }
+}
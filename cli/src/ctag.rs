@@ -1,6 +1,21 @@
+use crate::create_confluence_client;
+use crate::journal::{read_runs, JournalRun};
+use crate::labels::{
+    label_edits, labels_after, parse_labels, parse_updates, plan_rows, print_plan, LabelChange,
+    PlannedPage,
+};
+use crate::manifest::Manifest;
+use crate::output::{print_json, OutputFormat};
+use crate::propagate::Propagation;
+use crate::rules::RuleSet;
+use crate::taxonomy::Taxonomy;
 use clap::{Args, Subcommand};
-use nix_rust_template::{ConfluenceClient, ConfluenceConfig, ConfluencePage};
+use nix_rust_template::{ConfluenceClient, ConfluencePage};
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::error::Error;
+use std::path::PathBuf;
 
 /// ctag command: operate on Confluence page labels matched by a CQL expression.
 ///
@@ -9,6 +24,16 @@ use std::error::Error;
 ///   acli ctag add "parent = 1234" "foo,bar,baz"
 ///   acli ctag update "parent = 1234" "foo:bar,baz:foo"
 ///   acli ctag remove "parent = 1234" "foo,bar,baz"
+///   acli ctag stats "space = DOCS" --format json
+///   acli ctag rename kubernetes k8s
+///   acli ctag rename "howto,how-to,guide" how-to
+///   acli ctag plan labels.yaml
+///   acli ctag apply labels.yaml
+///   acli ctag rules run rules.yaml
+///   acli ctag propagate 1234 "runbook,ops" --exclude archived --sync
+///   acli ctag lint "space = OPS" --taxonomy taxonomy.yaml --fix
+///   acli ctag history
+///   acli ctag undo 20250101T120000Z-4242
 ///
 /// This module provides a CLI-friendly struct and a `run` function that applies
 /// the requested operation against one or more provided ConfluencePageTree
@@ -53,6 +78,132 @@ pub enum CtagOp {
         /// Comma-separated list of labels to remove (e.g., "foo,bar,baz")
         tags: String,
     },
+    /// Rename a label on every page of the site, or merge several synonyms into one
+    Rename {
+        /// Comma-separated list of labels to replace (e.g., "howto,guide")
+        old: String,
+        /// Label that replaces them
+        new: String,
+    },
+    /// Show the label actions needed to converge pages to a manifest
+    Plan {
+        /// YAML or JSON manifest of desired labels
+        manifest: PathBuf,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Apply the label actions needed to converge pages to a manifest
+    Apply {
+        /// YAML or JSON manifest of desired labels
+        manifest: PathBuf,
+    },
+    /// Label pages automatically from rules
+    Rules {
+        #[command(subcommand)]
+        operation: RulesOp,
+    },
+    /// Apply labels to a page and all of its descendants
+    Propagate {
+        /// ID of the root page
+        root: String,
+        /// Comma-separated list of labels to propagate (e.g., "foo,bar")
+        labels: String,
+        /// Maximum depth below the root page (0 labels only the root)
+        #[arg(long)]
+        depth: Option<usize>,
+        /// Comma-separated list of labels whose pages (and subtrees) are skipped
+        #[arg(long)]
+        exclude: Option<String>,
+        /// Also remove propagated labels from pages moved out of the subtree
+        #[arg(long)]
+        sync: bool,
+        /// Output format for the plan
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Check labels on pages matching the CQL expression against an approved taxonomy
+    Lint {
+        /// CQL expression selecting pages to operate on
+        cql: String,
+        /// YAML or JSON taxonomy file
+        #[arg(long)]
+        taxonomy: PathBuf,
+        /// Maximum edit distance for reporting near-duplicate labels
+        #[arg(long, default_value_t = 2)]
+        distance: usize,
+        /// Rewrite aliases to their canonical labels
+        #[arg(long)]
+        fix: bool,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Show past label changes recorded in the local journal
+    History {
+        /// Show the page changes of this run instead of listing runs
+        run_id: Option<String>,
+        /// Number of most recent runs to list
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Reverse the label changes of a past run, skipping pages modified since
+    Undo {
+        /// ID of the run to reverse (see `ctag history`)
+        run_id: String,
+    },
+    /// Show how often each label is used on pages matching the CQL expression
+    Stats {
+        /// CQL expression selecting pages to operate on
+        cql: String,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum RulesOp {
+    /// Evaluate a rules file, print the resulting plan and apply it
+    Run {
+        /// YAML or JSON rules file
+        rules: PathBuf,
+        /// CQL expression overriding the scope in the rules file
+        #[arg(long)]
+        cql: Option<String>,
+        /// Output format for the plan
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+}
+
+/// Label usage across a set of pages.
+#[derive(Debug, Serialize)]
+struct LabelStats {
+    /// Number of pages inspected
+    pages: usize,
+    /// Labels ordered by descending usage
+    labels: Vec<LabelUsage>,
+    /// Labels used on a single page, which are often typos
+    used_once: Vec<String>,
+}
+
+/// Usage of a single label.
+#[derive(Debug, Serialize)]
+struct LabelUsage {
+    label: String,
+    count: usize,
+    pages: Vec<PageRef>,
+}
+
+/// Minimal reference to a page for reports.
+#[derive(Debug, Clone, Serialize)]
+struct PageRef {
+    id: String,
+    title: String,
 }
 
 /// Execute the ctag command against the provided Confluence data.
@@ -68,7 +219,7 @@ pub enum CtagOp {
 pub fn run(
     cmd: &CtagCmd,
     dry_run: bool,
-    _pretty: bool,
+    pretty: bool,
     verbose: bool,
 ) -> Result<(), Box<dyn Error>> {
     match &cmd.operation {
@@ -107,122 +258,416 @@ pub fn run(
             }
         }
         CtagOp::Add { cql, tags } => {
+            let change = LabelChange::Add(parse_labels(tags));
+            change_labels(cql, &change, "add", dry_run, verbose)?;
+        }
+        CtagOp::Update { cql, tags } => {
+            let change = LabelChange::Update(parse_updates(tags)?);
+            change_labels(cql, &change, "update", dry_run, verbose)?;
+        }
+        CtagOp::Remove { cql, tags } => {
+            let change = LabelChange::Remove(parse_labels(tags));
+            change_labels(cql, &change, "remove", dry_run, verbose)?;
+        }
+        CtagOp::Rename { old, new } => {
+            let new = new.trim();
+            // Merging into a label that is also listed as old keeps that label
+            let old_labels: Vec<&str> = old
+                .split(',')
+                .map(|s: &str| s.trim())
+                .filter(|s| !s.is_empty() && *s != new)
+                .collect();
+            if old_labels.is_empty() {
+                return Err(format!("Nothing to rename: no labels other than '{new}'").into());
+            }
             if verbose {
-                eprintln!("Adding labels '{tags}' to pages matching: {cql}");
+                eprintln!("Renaming labels {old_labels:?} to '{new}' across all spaces");
             }
-            // Parse comma-separated tags
-            let tag_list: Vec<&str> = tags.split(',').map(|s: &str| s.trim()).collect();
 
-            if dry_run {
-                println!("DRY RUN: Would add labels {tag_list:?} to pages matching CQL: {cql}");
-            } else {
-                // Create Confluence client and execute query
-                let client = create_confluence_client()?;
-                let pages = client.query_pages_by_cql(cql)?;
-
-                if pages.is_empty() {
-                    println!("No pages found matching CQL: {cql}");
-                    return Ok(());
+            let client = create_confluence_client()?;
+            let mut pages: Vec<ConfluencePage> = Vec::new();
+            let mut seen: HashSet<String> = HashSet::new();
+            for label in &old_labels {
+                for page in client.query_all_pages_by_cql(&format!("label = \"{label}\""))? {
+                    if seen.insert(page.id.clone()) {
+                        pages.push(page);
+                    }
                 }
+            }
 
-                println!("Adding labels {:?} to {} pages...", tag_list, pages.len());
-
-                // Extract page IDs for bulk operation
-                let page_ids: Vec<&str> = pages.iter().map(|p| p.id.as_str()).collect();
+            if pages.is_empty() {
+                println!("No pages found with labels {old_labels:?}");
+                return Ok(());
+            }
 
-                // Use bulk operation for efficiency
-                client.bulk_add_labels(&page_ids, &tag_list)?;
+            let journal = if dry_run {
+                None
+            } else {
+                Some(JournalRun::start()?)
+            };
+            for page in &pages {
+                let before = get_page_labels(page);
+                let replaced: Vec<&str> = old_labels
+                    .iter()
+                    .copied()
+                    .filter(|l| before.iter().any(|b| b == l))
+                    .collect();
+                let mut after: Vec<String> = before
+                    .iter()
+                    .filter(|b| !replaced.contains(&b.as_str()))
+                    .cloned()
+                    .collect();
+                let has_new = before.iter().any(|b| b == new);
+                if !has_new {
+                    after.push(new.to_string());
+                }
 
-                println!("Successfully added labels to {} pages:", pages.len());
-                for page in &pages {
-                    println!("  - {}", page.title);
+                let Some(journal) = &journal else {
+                    println!(
+                        "DRY RUN: Would replace {replaced:?} with '{new}' on '{}'",
+                        page.title
+                    );
+                    continue;
+                };
+
+                // Add the new label before removing the old ones so the page is
+                // never left without either.
+                if !has_new {
+                    client.add_page_labels(&page.id, &[new])?;
                 }
+                client.remove_page_labels(&page.id, &replaced)?;
+                journal.record(&page.id, &page.title, &before, &after)?;
+                println!("Replaced {replaced:?} with '{new}' on '{}'", page.title);
+            }
+
+            match &journal {
+                Some(journal) => println!(
+                    "Renamed {old_labels:?} to '{new}' on {} pages (run {})",
+                    pages.len(),
+                    journal.id()
+                ),
+                None => println!(
+                    "DRY RUN: Would rename {old_labels:?} to '{new}' on {} pages",
+                    pages.len()
+                ),
             }
         }
-        CtagOp::Update { cql, tags } => {
+        CtagOp::Plan { manifest, format } => {
+            let manifest = Manifest::load(manifest)?;
+            let client = create_confluence_client()?;
+            let planned = manifest.plan(&client, verbose)?;
+
+            match format {
+                OutputFormat::Json => print_json(&plan_rows(&planned), pretty)?,
+                OutputFormat::Table => print_plan(&planned, "pages"),
+            }
+        }
+        CtagOp::Apply { manifest } => {
+            let manifest = Manifest::load(manifest)?;
+            let client = create_confluence_client()?;
+            let planned = manifest.plan(&client, verbose)?;
+            print_plan(&planned, "pages");
+            apply_plan(&client, &planned, dry_run, verbose)?;
+        }
+        CtagOp::Rules {
+            operation: RulesOp::Run { rules, cql, format },
+        } => {
+            let rules = RuleSet::load(rules)?;
+            let client = create_confluence_client()?;
+            let planned = rules.plan(&client, cql.as_deref(), verbose)?;
+            match format {
+                OutputFormat::Json => print_json(&plan_rows(&planned), pretty)?,
+                OutputFormat::Table => print_plan(&planned, "pages"),
+            }
+            apply_plan(&client, &planned, dry_run, verbose)?;
+        }
+        CtagOp::Propagate {
+            root,
+            labels,
+            depth,
+            exclude,
+            sync,
+            format,
+        } => {
+            let label_list: Vec<&str> = labels.split(',').map(|s: &str| s.trim()).collect();
+            let exclude_list: Vec<&str> = exclude
+                .as_deref()
+                .map(|e| e.split(',').map(|s: &str| s.trim()).collect())
+                .unwrap_or_default();
+            let propagation = Propagation {
+                root_id: root,
+                labels: &label_list,
+                max_depth: *depth,
+                exclude: &exclude_list,
+                sync: *sync,
+            };
+
+            let client = create_confluence_client()?;
+            let planned = propagation.plan(&client, verbose)?;
+            match format {
+                OutputFormat::Json => print_json(&plan_rows(&planned), pretty)?,
+                OutputFormat::Table => print_plan(&planned, "pages"),
+            }
+            apply_plan(&client, &planned, dry_run, verbose)?;
+            if !dry_run {
+                propagation.record(&client, &planned)?;
+            }
+        }
+        CtagOp::Lint {
+            cql,
+            taxonomy,
+            distance,
+            fix,
+            format,
+        } => {
+            let taxonomy = Taxonomy::load(taxonomy)?;
             if verbose {
-                eprintln!("Updating labels '{tags}' on pages matching: {cql}");
+                eprintln!("Linting labels on pages matching: {cql}");
             }
-            // Parse colon-separated tag updates
-            let updates: Result<Vec<(String, String)>, Box<dyn Error>> = tags
-                .split(',')
-                .map(|s: &str| s.trim())
-                .map(|update: &str| -> Result<(String, String), Box<dyn Error>> {
-                    let parts: Vec<&str> = update.split(':').collect();
-                    if parts.len() != 2 {
-                        return Err(format!(
-                            "Invalid update format '{update}'. Expected 'old:new'"
-                        )
-                        .into());
+            let client = create_confluence_client()?;
+            let pages = client.query_all_pages_by_cql(cql)?;
+            let report = taxonomy.lint(&pages, *distance);
+
+            match format {
+                OutputFormat::Json => print_json(&report, pretty)?,
+                OutputFormat::Table => {
+                    for finding in &report.findings {
+                        println!("{} ({})", finding.title, finding.id);
+                        for unapproved in &finding.unapproved {
+                            match &unapproved.canonical {
+                                Some(canonical) => println!(
+                                    "  unapproved label '{}' (alias of '{canonical}')",
+                                    unapproved.label
+                                ),
+                                None => println!("  unapproved label '{}'", unapproved.label),
+                            }
+                        }
+                        for missing in &finding.missing {
+                            println!("  missing required label '{missing}'");
+                        }
                     }
-                    Ok((parts[0].trim().to_string(), parts[1].trim().to_string()))
-                })
-                .collect();
-
-            let updates = updates?;
-
-            if dry_run {
-                println!("DRY RUN: Would update labels {updates:?} on pages matching CQL: {cql}");
-            } else {
-                // Create Confluence client and execute query
-                let client = create_confluence_client()?;
-                let pages = client.query_pages_by_cql(cql)?;
+                    if !report.near_duplicates.is_empty() {
+                        println!("Near-duplicate labels:");
+                        for pair in &report.near_duplicates {
+                            println!("  '{}' ~ '{}' (distance {})", pair.a, pair.b, pair.distance);
+                        }
+                    }
+                    println!(
+                        "{} of {} pages have label problems; {} near-duplicate pairs",
+                        report.findings.len(),
+                        report.pages,
+                        report.near_duplicates.len()
+                    );
+                }
+            }
 
-                if pages.is_empty() {
-                    println!("No pages found matching CQL: {cql}");
+            if *fix {
+                // Group pages by alias so each rewrite goes through the bulk update path.
+                let mut by_alias: BTreeMap<(&str, &str), Vec<&ConfluencePage>> = BTreeMap::new();
+                for page in &pages {
+                    for label in get_page_labels(page) {
+                        if let Some((alias, canonical)) = taxonomy.aliases.get_key_value(&label) {
+                            by_alias
+                                .entry((alias.as_str(), canonical.as_str()))
+                                .or_default()
+                                .push(page);
+                        }
+                    }
+                }
+                if by_alias.is_empty() {
+                    println!("No aliases to fix");
                     return Ok(());
                 }
 
-                println!("Updating labels {:?} on {} pages...", updates, pages.len());
-
-                // Extract page IDs for bulk operation
-                let page_ids: Vec<&str> = pages.iter().map(|p| p.id.as_str()).collect();
-
-                // Use bulk operation for efficiency
-                client.bulk_update_labels(&page_ids, &updates)?;
-
-                println!("Successfully updated labels on {} pages:", pages.len());
-                for page in &pages {
-                    println!("  - {}", page.title);
+                let journal = if dry_run {
+                    None
+                } else {
+                    Some(JournalRun::start()?)
+                };
+                // A page may carry several aliases; track its labels across rewrites.
+                let mut current: HashMap<&str, Vec<String>> = HashMap::new();
+                for ((alias, canonical), alias_pages) in &by_alias {
+                    let Some(journal) = &journal else {
+                        println!(
+                            "DRY RUN: Would rewrite '{alias}' to '{canonical}' on {} pages",
+                            alias_pages.len()
+                        );
+                        continue;
+                    };
+
+                    let page_ids: Vec<&str> = alias_pages.iter().map(|p| p.id.as_str()).collect();
+                    let updates = vec![(alias.to_string(), canonical.to_string())];
+                    client.bulk_update_labels(&page_ids, &updates)?;
+                    for page in alias_pages {
+                        let labels = current
+                            .entry(page.id.as_str())
+                            .or_insert_with(|| get_page_labels(page));
+                        let after = labels_after(labels, &[canonical], &[alias]);
+                        journal.record(&page.id, &page.title, labels, &after)?;
+                        *labels = after;
+                    }
+                    println!(
+                        "Rewrote '{alias}' to '{canonical}' on {} pages",
+                        alias_pages.len()
+                    );
                 }
             }
         }
-        CtagOp::Remove { cql, tags } => {
+        CtagOp::History {
+            run_id,
+            limit,
+            format,
+        } => {
+            let mut runs = read_runs()?;
+            runs.reverse();
+
+            match run_id {
+                Some(run_id) => {
+                    let run = runs
+                        .into_iter()
+                        .find(|r| &r.run_id == run_id)
+                        .ok_or_else(|| format!("No run {run_id} in the journal"))?;
+                    match format {
+                        OutputFormat::Json => print_json(&run, pretty)?,
+                        OutputFormat::Table => {
+                            println!("{} {}  {}", run.run_id, run.time, run.command);
+                            for change in &run.changes {
+                                println!("  {} ({})", change.title, change.page_id);
+                                println!("    before: [{}]", change.before.join(", "));
+                                println!("    after:  [{}]", change.after.join(", "));
+                            }
+                        }
+                    }
+                }
+                None => {
+                    runs.truncate(*limit);
+                    match format {
+                        OutputFormat::Json => print_json(&runs, pretty)?,
+                        OutputFormat::Table => {
+                            if runs.is_empty() {
+                                println!("No label changes recorded.");
+                            }
+                            for run in &runs {
+                                println!(
+                                    "{}  {}  {:>5} pages  {}",
+                                    run.run_id,
+                                    run.time.format("%Y-%m-%d %H:%M:%S"),
+                                    run.changes.len(),
+                                    run.command
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        CtagOp::Undo { run_id } => {
+            let run = read_runs()?
+                .into_iter()
+                .find(|r| &r.run_id == run_id)
+                .ok_or_else(|| format!("No run {run_id} in the journal"))?;
             if verbose {
-                eprintln!("Removing labels '{tags}' from pages matching: {cql}");
+                eprintln!(
+                    "Undoing run {run_id} ({} page changes): {}",
+                    run.changes.len(),
+                    run.command
+                );
             }
-            // Parse comma-separated tags
-            let tag_list: Vec<&str> = tags.split(',').map(|s: &str| s.trim()).collect();
 
-            if dry_run {
-                println!(
-                    "DRY RUN: Would remove labels {tag_list:?} from pages matching CQL: {cql}"
-                );
+            let client = create_confluence_client()?;
+            let journal = if dry_run {
+                None
             } else {
-                // Create Confluence client and execute query
-                let client = create_confluence_client()?;
-                let pages = client.query_pages_by_cql(cql)?;
-
-                if pages.is_empty() {
-                    println!("No pages found matching CQL: {cql}");
-                    return Ok(());
+                Some(JournalRun::start()?)
+            };
+            let mut reverted = 0;
+            let mut skipped = 0;
+            for change in run.changes.iter().rev() {
+                let mut current = client.get_page_labels(&change.page_id)?;
+                current.sort();
+                if current != change.after {
+                    println!(
+                        "Skipping '{}' ({}): labels changed since run {run_id}",
+                        change.title, change.page_id
+                    );
+                    skipped += 1;
+                    continue;
                 }
 
+                let to_add: Vec<&str> = change
+                    .before
+                    .iter()
+                    .filter(|l| !change.after.contains(l))
+                    .map(String::as_str)
+                    .collect();
+                let to_remove: Vec<&str> = change
+                    .after
+                    .iter()
+                    .filter(|l| !change.before.contains(l))
+                    .map(String::as_str)
+                    .collect();
+
+                let Some(journal) = &journal else {
+                    println!(
+                        "DRY RUN: Would restore '{}': add {to_add:?}, remove {to_remove:?}",
+                        change.title
+                    );
+                    reverted += 1;
+                    continue;
+                };
+
+                if !to_add.is_empty() {
+                    client.add_page_labels(&change.page_id, &to_add)?;
+                }
+                client.remove_page_labels(&change.page_id, &to_remove)?;
+                journal.record(&change.page_id, &change.title, &current, &change.before)?;
                 println!(
-                    "Removing labels {:?} from {} pages...",
-                    tag_list,
-                    pages.len()
+                    "Restored '{}': added {to_add:?}, removed {to_remove:?}",
+                    change.title
                 );
+                reverted += 1;
+            }
 
-                // Extract page IDs for bulk operation
-                let page_ids: Vec<&str> = pages.iter().map(|p| p.id.as_str()).collect();
-
-                // Use bulk operation for efficiency
-                client.bulk_remove_labels(&page_ids, &tag_list)?;
-
-                println!("Successfully removed labels from {} pages:", pages.len());
-                for page in &pages {
-                    println!("  - {}", page.title);
+            let prefix = if dry_run {
+                "DRY RUN: Would restore"
+            } else {
+                "Restored"
+            };
+            println!(
+                "{prefix} {reverted} pages from run {run_id}; skipped {skipped} modified pages"
+            );
+        }
+        CtagOp::Stats { cql, format } => {
+            if verbose {
+                eprintln!("Collecting label statistics for pages matching: {cql}");
+            }
+            let client = create_confluence_client()?;
+            let pages = client.query_all_pages_by_cql(cql)?;
+            let stats = label_stats(&pages);
+
+            match format {
+                OutputFormat::Json => print_json(&stats, pretty)?,
+                OutputFormat::Table => {
+                    if stats.labels.is_empty() {
+                        println!("No labels found on {} pages", stats.pages);
+                        return Ok(());
+                    }
+                    println!(
+                        "{} labels across {} pages:",
+                        stats.labels.len(),
+                        stats.pages
+                    );
+                    for usage in &stats.labels {
+                        println!("  {:>6}  {}", usage.count, usage.label);
+                    }
+                    if !stats.used_once.is_empty() {
+                        println!();
+                        println!("Labels used only once (possible typos):");
+                        for usage in stats.labels.iter().filter(|u| u.count == 1) {
+                            let page = &usage.pages[0];
+                            println!("  {}  on '{}' ({})", usage.label, page.title, page.id);
+                        }
+                    }
                 }
             }
         }
@@ -230,24 +675,151 @@ pub fn run(
     Ok(())
 }
 
-/// Create a Confluence client using environment variables.
-fn create_confluence_client() -> Result<ConfluenceClient, Box<dyn Error>> {
-    dotenv::dotenv().ok(); // Load .env file, ignore if not found
-
-    let base_url =
-        std::env::var("ATLASSIAN_URL").map_err(|_| "ATLASSIAN_URL environment variable not set")?;
-    let username = std::env::var("ATLASSIAN_USERNAME")
-        .map_err(|_| "ATLASSIAN_USERNAME environment variable not set")?;
-    let api_token = std::env::var("ATLASSIAN_API_TOKEN")
-        .map_err(|_| "ATLASSIAN_API_TOKEN environment variable not set")?;
-
-    let config = ConfluenceConfig {
-        base_url,
-        username,
-        api_token,
-    };
+/// Plan a label change on the pages matching a CQL expression, then print
+/// the plan (dry run) or apply exactly the planned actions.
+fn change_labels(
+    cql: &str,
+    change: &LabelChange,
+    verb: &str,
+    dry_run: bool,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+    if verbose {
+        eprintln!("Planning label {verb} on pages matching: {cql}");
+    }
+    let client = create_confluence_client()?;
+    let pages = client.query_pages_by_cql(cql)?;
+    if pages.is_empty() {
+        println!("No pages found matching CQL: {cql}");
+        return Ok(());
+    }
+
+    let planned: Vec<PlannedPage> = pages
+        .iter()
+        .filter_map(|page| change.plan(&page.id, &page.title, &get_page_labels(page)))
+        .collect();
+
+    if dry_run {
+        println!("DRY RUN: Would {verb} labels on pages matching CQL: {cql}");
+        print_plan(&planned, "pages");
+        return Ok(());
+    }
+    if planned.is_empty() {
+        println!("No changes. Labels are already up to date.");
+        return Ok(());
+    }
+
+    let journal = JournalRun::start()?;
+    for page in &planned {
+        let (add, remove) = label_edits(page);
+        // Add new labels before removing old ones so an updated page is never
+        // left without either.
+        if !add.is_empty() {
+            client.add_page_labels(&page.tree.root_page, &add)?;
+        }
+        if !remove.is_empty() {
+            client.remove_page_labels(&page.tree.root_page, &remove)?;
+        }
+        journal.record(
+            &page.tree.root_page,
+            &page.title,
+            &page.before,
+            &page.tree.current_page_labels,
+        )?;
+        if verbose {
+            eprintln!(
+                "Applied {} actions to '{}'",
+                page.tree.tag_actions.len(),
+                page.title
+            );
+        }
+    }
+
+    println!(
+        "Successfully changed labels on {} of {} pages (run {}):",
+        planned.len(),
+        pages.len(),
+        journal.id()
+    );
+    for page in &planned {
+        println!("  - {}", page.title);
+    }
+    Ok(())
+}
 
-    ConfluenceClient::new(config).map_err(|e| e.into())
+/// Apply planned label actions, recording every page change in the journal.
+fn apply_plan(
+    client: &ConfluenceClient,
+    planned: &[PlannedPage],
+    dry_run: bool,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+    if planned.is_empty() {
+        return Ok(());
+    }
+    if dry_run {
+        println!("DRY RUN: Would apply the plan above");
+        return Ok(());
+    }
+
+    let journal = JournalRun::start()?;
+    for page in planned {
+        page.tree.apply_actions(client, dry_run)?;
+        journal.record(
+            &page.tree.root_page,
+            &page.title,
+            &page.before,
+            &page.tree.current_page_labels,
+        )?;
+        if verbose {
+            eprintln!(
+                "Applied {} actions to '{}'",
+                page.tree.tag_actions.len(),
+                page.title
+            );
+        }
+    }
+    println!(
+        "Applied plan to {} pages (run {})",
+        planned.len(),
+        journal.id()
+    );
+    Ok(())
+}
+
+/// Aggregate label frequencies and the pages carrying each label.
+fn label_stats(pages: &[ConfluencePage]) -> LabelStats {
+    let mut by_label: BTreeMap<String, Vec<PageRef>> = BTreeMap::new();
+    for page in pages {
+        for label in get_page_labels(page) {
+            by_label.entry(label).or_default().push(PageRef {
+                id: page.id.clone(),
+                title: page.title.clone(),
+            });
+        }
+    }
+
+    let mut labels: Vec<LabelUsage> = by_label
+        .into_iter()
+        .map(|(label, pages)| LabelUsage {
+            label,
+            count: pages.len(),
+            pages,
+        })
+        .collect();
+    labels.sort_by_key(|u| Reverse(u.count));
+
+    let used_once = labels
+        .iter()
+        .filter(|u| u.count == 1)
+        .map(|u| u.label.clone())
+        .collect();
+
+    LabelStats {
+        pages: pages.len(),
+        labels,
+        used_once,
+    }
 }
 
 /// Display pages in a tree format similar to the unix tree command.
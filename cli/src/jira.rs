@@ -0,0 +1,66 @@
+//! Jira commands.
+
+use clap::{Args, Subcommand};
+use std::error::Error;
+
+pub mod comment;
+pub mod graph;
+pub mod issue;
+pub mod sprint;
+pub mod transition;
+pub mod worklog;
+
+/// jira command: work with Jira issues.
+///
+/// Intended usage examples:
+///   acli jira issue create --project OPS --type Bug --summary "Disk full on db-1"
+///   acli jira issue get OPS-123
+///   acli jira issue edit OPS-123 --set "Story Points=3"
+///   acli jira issue delete OPS-123
+///   acli jira issue transitions OPS-123
+///   acli jira transition "project = OPS and status = 'In Review'" Done --resolution Fixed
+///   acli jira comment add OPS-123 "Fixed in **1.4.2**"
+///   acli jira worklog add OPS-123 --time-spent "1h 30m" --comment "Incident review"
+///   acli jira worklog report "project = OPS" --by user --since 2024-03-01
+///   acli jira sprint list "OPS board" --state active,future
+///   acli jira sprint move "project = OPS and labels = next" 42
+///   acli jira sprint report 42 --points-field "Story Points"
+///   acli jira graph "fixVersion = 1.4" --format dot | dot -Tsvg > release.svg
+#[derive(Args, Debug)]
+pub struct JiraCmd {
+    #[command(subcommand)]
+    pub operation: JiraOp,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum JiraOp {
+    /// Create, view, edit and delete issues
+    Issue(issue::IssueCmd),
+    /// Move every issue matching a JQL query to a status
+    Transition(transition::TransitionArgs),
+    /// List and add issue comments
+    Comment(comment::CommentCmd),
+    /// List and log time on issues, and report logged time
+    Worklog(worklog::WorklogCmd),
+    /// List boards and sprints, plan and run sprints, and report on them
+    Sprint(sprint::SprintCmd),
+    /// Export the link graph of issues matching a JQL query
+    Graph(graph::GraphArgs),
+}
+
+/// Execute the jira command.
+pub fn run(
+    cmd: &JiraCmd,
+    dry_run: bool,
+    pretty: bool,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+    match &cmd.operation {
+        JiraOp::Issue(cmd) => issue::run(cmd, dry_run, pretty, verbose),
+        JiraOp::Transition(args) => transition::run(args, dry_run, pretty, verbose),
+        JiraOp::Comment(cmd) => comment::run(cmd, dry_run, pretty, verbose),
+        JiraOp::Worklog(cmd) => worklog::run(cmd, dry_run, pretty, verbose),
+        JiraOp::Sprint(cmd) => sprint::run(cmd, dry_run, pretty, verbose),
+        JiraOp::Graph(args) => graph::run(args, dry_run, pretty, verbose),
+    }
+}
@@ -0,0 +1,130 @@
+//! `jira comment`: list and add comments on an issue.
+//!
+//! Comments are shown and written as Markdown and sent to Jira as ADF.
+
+use crate::create_jira_client;
+use crate::jira::issue::user_name;
+use crate::output::{print_json, OutputFormat};
+use clap::{Args, Subcommand};
+use nix_rust_template::{adf_to_markdown, markdown_to_adf};
+use serde::Serialize;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Args, Debug)]
+pub struct CommentCmd {
+    #[command(subcommand)]
+    pub operation: CommentOp,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CommentOp {
+    /// List the comments on an issue, oldest first
+    List {
+        /// Issue key
+        key: String,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Add a comment to an issue
+    Add {
+        /// Issue key
+        key: String,
+        /// Comment text, in Markdown
+        #[arg(required_unless_present = "file", conflicts_with = "file")]
+        body: Option<String>,
+        /// Markdown file holding the comment text
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+}
+
+/// A comment with its body as Markdown, for JSON output.
+#[derive(Debug, Serialize)]
+struct CommentRow {
+    id: String,
+    author: String,
+    created: Option<String>,
+    updated: Option<String>,
+    body: String,
+}
+
+/// Execute the jira comment command.
+pub fn run(
+    cmd: &CommentCmd,
+    dry_run: bool,
+    pretty: bool,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+    let client = create_jira_client()?;
+
+    match &cmd.operation {
+        CommentOp::List { key, format } => {
+            if verbose {
+                eprintln!("Fetching comments of {key}");
+            }
+            let rows: Vec<CommentRow> = client
+                .get_comments(key)?
+                .into_iter()
+                .map(|comment| CommentRow {
+                    id: comment.id,
+                    author: comment.author.as_ref().map(user_name).unwrap_or_default(),
+                    created: comment.created,
+                    updated: comment.updated,
+                    body: comment
+                        .body
+                        .as_ref()
+                        .map(adf_to_markdown)
+                        .unwrap_or_default(),
+                })
+                .collect();
+
+            match format {
+                OutputFormat::Json => print_json(&rows, pretty)?,
+                OutputFormat::Table => {
+                    if rows.is_empty() {
+                        println!("No comments on {key}.");
+                    }
+                    for (i, row) in rows.iter().enumerate() {
+                        if i > 0 {
+                            println!();
+                        }
+                        println!(
+                            "#{} {} {}",
+                            row.id,
+                            row.author,
+                            row.created.as_deref().unwrap_or_default()
+                        );
+                        for line in row.body.lines() {
+                            println!("  {line}");
+                        }
+                    }
+                }
+            }
+        }
+        CommentOp::Add { key, body, file } => {
+            let markdown = match (body, file) {
+                (Some(body), _) => body.clone(),
+                (None, Some(path)) => fs::read_to_string(path)?,
+                (None, None) => return Err("Pass the comment text or --file".into()),
+            };
+            if markdown.trim().is_empty() {
+                return Err("Comment is empty".into());
+            }
+            let body = markdown_to_adf(&markdown);
+
+            if dry_run {
+                println!("DRY RUN: Would add comment to {key}:");
+                print_json(&body, true)?;
+                return Ok(());
+            }
+
+            let comment = client.add_comment(key, &body)?;
+            println!("Added comment #{} to {key}", comment.id);
+        }
+    }
+
+    Ok(())
+}
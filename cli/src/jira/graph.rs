@@ -0,0 +1,261 @@
+//! `jira graph`: export the dependency graph of the issues matching a JQL
+//! query as Graphviz, Mermaid or JSON.
+//!
+//! Links are followed outward from the matching issues. Cycles and the
+//! longest blocked-by chain are reported on stderr for Graphviz and Mermaid
+//! output, so the graph itself can be piped straight into a renderer, and are
+//! included in JSON output.
+
+use crate::create_jira_client;
+use crate::output::print_json;
+use clap::{Args, ValueEnum};
+use nix_rust_template::{IssueEdge, IssueGraph, IssueGraphOptions, IssueNode};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::error::Error;
+
+#[derive(Args, Debug)]
+pub struct GraphArgs {
+    /// JQL query selecting the root issues
+    pub jql: String,
+    /// Output format
+    #[arg(long, value_enum, default_value_t)]
+    pub format: GraphFormat,
+    /// Number of links to follow from the root issues; unlimited by default
+    #[arg(long)]
+    pub depth: Option<usize>,
+    /// Only follow links of this type, e.g. "Blocks" (repeatable)
+    #[arg(long = "link-type")]
+    pub link_types: Vec<String>,
+    /// Do not follow subtasks and parents
+    #[arg(long)]
+    pub no_subtasks: bool,
+}
+
+/// Graph output format.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// Graphviz DOT
+    #[default]
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+    /// JSON with nodes, edges, cycles and the longest blocked-by chain
+    Json,
+}
+
+/// The graph with its analysis, for JSON output.
+#[derive(Debug, Serialize)]
+struct GraphReport<'a> {
+    nodes: Vec<&'a IssueNode>,
+    edges: &'a [IssueEdge],
+    cycles: &'a [Vec<String>],
+    longest_blocked_chain: &'a [String],
+}
+
+/// Execute the jira graph command.
+pub fn run(
+    args: &GraphArgs,
+    _dry_run: bool,
+    pretty: bool,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+    let client = create_jira_client()?;
+
+    let options = IssueGraphOptions {
+        max_depth: args.depth,
+        link_types: args.link_types.clone(),
+        include_parents: !args.no_subtasks,
+    };
+    if verbose {
+        eprintln!("Following links from issues matching '{}'", args.jql);
+    }
+    let graph = client.get_issue_graph_for_jql(&args.jql, &options)?;
+    if verbose {
+        eprintln!(
+            "Found {} issues and {} links",
+            graph.nodes.len(),
+            graph.edges.len()
+        );
+    }
+
+    let cycles = graph.cycles();
+    let chain = graph.longest_blocked_chain();
+    match args.format {
+        GraphFormat::Json => {
+            let report = GraphReport {
+                nodes: graph.nodes.values().collect(),
+                edges: &graph.edges,
+                cycles: &cycles,
+                longest_blocked_chain: &chain,
+            };
+            print_json(&report, pretty)?;
+        }
+        GraphFormat::Dot => {
+            print!("{}", to_dot(&graph, &cycles, &chain));
+            print_analysis(&cycles, &chain);
+        }
+        GraphFormat::Mermaid => {
+            print!("{}", to_mermaid(&graph, &cycles, &chain));
+            print_analysis(&cycles, &chain);
+        }
+    }
+
+    Ok(())
+}
+
+/// Report cycles and the longest blocked-by chain on stderr.
+fn print_analysis(cycles: &[Vec<String>], chain: &[String]) {
+    for cycle in cycles {
+        eprintln!("Cycle: {}", cycle.join(", "));
+    }
+    if chain.is_empty() {
+        eprintln!("No blocked-by chains.");
+    } else {
+        eprintln!(
+            "Longest blocked-by chain ({} issues): {}",
+            chain.len(),
+            chain.join(" <- ")
+        );
+    }
+}
+
+/// Blocking edges to highlight: those inside a cycle, and those along the
+/// longest blocked-by chain.
+struct Highlights<'a> {
+    cycles: &'a [Vec<String>],
+    chain_links: BTreeSet<(&'a str, &'a str)>,
+}
+
+impl<'a> Highlights<'a> {
+    fn new(cycles: &'a [Vec<String>], chain: &'a [String]) -> Self {
+        // The chain starts with the most blocked issue, so each issue is
+        // blocked by the one after it.
+        let chain_links = chain
+            .windows(2)
+            .map(|pair| (pair[1].as_str(), pair[0].as_str()))
+            .collect();
+        Highlights {
+            cycles,
+            chain_links,
+        }
+    }
+
+    fn in_cycle(&self, edge: &IssueEdge) -> bool {
+        edge.is_blocking()
+            && self
+                .cycles
+                .iter()
+                .any(|c| c.contains(&edge.from) && c.contains(&edge.to))
+    }
+
+    fn on_chain(&self, edge: &IssueEdge) -> bool {
+        edge.is_blocking()
+            && self
+                .chain_links
+                .contains(&(edge.from.as_str(), edge.to.as_str()))
+    }
+}
+
+/// Render the graph as Graphviz DOT.
+fn to_dot(graph: &IssueGraph, cycles: &[Vec<String>], chain: &[String]) -> String {
+    let highlights = Highlights::new(cycles, chain);
+    let mut out = String::from("digraph issues {\n  rankdir=LR;\n  node [shape=box];\n");
+
+    for node in graph.nodes.values() {
+        let mut label = format!("{}\\n{}", node.key, dot_escape(&node.summary));
+        if let Some(status) = &node.status {
+            label.push_str(&format!("\\n[{}]", dot_escape(status)));
+        }
+        let mut attrs = vec![format!("label=\"{label}\"")];
+        if node.status_category.as_deref() == Some("done") {
+            attrs.push("style=dashed".to_string());
+        }
+        if node.root {
+            attrs.push("penwidth=2".to_string());
+        }
+        out.push_str(&format!("  \"{}\" [{}];\n", node.key, attrs.join(", ")));
+    }
+
+    for edge in &graph.edges {
+        let mut attrs = vec![format!("label=\"{}\"", dot_escape(&edge.relation))];
+        if highlights.in_cycle(edge) {
+            attrs.push("color=red".to_string());
+        }
+        if highlights.on_chain(edge) {
+            attrs.push("penwidth=3".to_string());
+        }
+        if !edge.is_blocking() {
+            attrs.push("style=dotted".to_string());
+        }
+        out.push_str(&format!(
+            "  \"{}\" -> \"{}\" [{}];\n",
+            edge.from,
+            edge.to,
+            attrs.join(", ")
+        ));
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// Render the graph as a Mermaid flowchart.
+fn to_mermaid(graph: &IssueGraph, cycles: &[Vec<String>], chain: &[String]) -> String {
+    let highlights = Highlights::new(cycles, chain);
+    let mut out = String::from("flowchart LR\n");
+
+    for node in graph.nodes.values() {
+        let mut label = format!("{}: {}", node.key, mermaid_escape(&node.summary));
+        if let Some(status) = &node.status {
+            label.push_str(&format!("<br/>[{}]", mermaid_escape(status)));
+        }
+        out.push_str(&format!("  {}[\"{label}\"]\n", mermaid_id(&node.key)));
+    }
+
+    let mut cycle_links = Vec::new();
+    let mut chain_links = Vec::new();
+    for (i, edge) in graph.edges.iter().enumerate() {
+        let arrow = if edge.is_blocking() { "-->" } else { "-.->" };
+        out.push_str(&format!(
+            "  {} {arrow}|{}| {}\n",
+            mermaid_id(&edge.from),
+            mermaid_escape(&edge.relation),
+            mermaid_id(&edge.to)
+        ));
+        if highlights.in_cycle(edge) {
+            cycle_links.push(i.to_string());
+        } else if highlights.on_chain(edge) {
+            chain_links.push(i.to_string());
+        }
+    }
+
+    if !cycle_links.is_empty() {
+        out.push_str(&format!(
+            "  linkStyle {} stroke:red,stroke-width:2px\n",
+            cycle_links.join(",")
+        ));
+    }
+    if !chain_links.is_empty() {
+        out.push_str(&format!(
+            "  linkStyle {} stroke-width:4px\n",
+            chain_links.join(",")
+        ));
+    }
+    out
+}
+
+/// Escape text inside a quoted DOT string.
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Escape text inside a Mermaid label.
+fn mermaid_escape(text: &str) -> String {
+    text.replace('"', "#quot;").replace('|', "#124;")
+}
+
+/// Mermaid node ID for an issue key; hyphens are not allowed in IDs.
+fn mermaid_id(key: &str) -> String {
+    key.replace('-', "_")
+}
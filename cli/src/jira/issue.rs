@@ -0,0 +1,468 @@
+//! `jira issue`: create, view, edit and delete issues.
+//!
+//! Fields given with `--set` or in a YAML file may be named by ID
+//! (`customfield_10042`) or by display name (`Story Points`). Values are
+//! checked against the field schema and the create or edit screen before
+//! anything is sent, so a misspelt option or unknown user fails early.
+//! Rich-text fields such as the description are written and shown as
+//! Markdown.
+
+use crate::create_jira_client;
+use crate::output::{print_json, OutputFormat};
+use clap::{Args, Subcommand};
+use nix_rust_template::{
+    adf_to_markdown, FieldRegistry, JiraClient, JiraFieldMeta, JiraIssue, JiraUser,
+};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Args, Debug)]
+pub struct IssueCmd {
+    #[command(subcommand)]
+    pub operation: IssueOp,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum IssueOp {
+    /// Create an issue from flags and/or a YAML file of fields
+    Create {
+        /// Project key
+        #[arg(long)]
+        project: Option<String>,
+        /// Issue type name (defaults to Task)
+        #[arg(long = "type")]
+        issue_type: Option<String>,
+        /// Issue summary
+        #[arg(long)]
+        summary: Option<String>,
+        /// Description, in Markdown
+        #[arg(long)]
+        description: Option<String>,
+        /// Comma-separated list of labels
+        #[arg(long)]
+        labels: Option<String>,
+        /// Assignee account ID, display name or email
+        #[arg(long)]
+        assignee: Option<String>,
+        /// Priority name
+        #[arg(long)]
+        priority: Option<String>,
+        /// Parent issue key
+        #[arg(long)]
+        parent: Option<String>,
+        /// Set a field by name or ID (repeatable, e.g. "Story Points=3")
+        #[arg(long = "set", value_name = "FIELD=VALUE")]
+        set: Vec<String>,
+        /// YAML or JSON file mapping field names or IDs to values
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Show an issue
+    Get {
+        /// Issue key
+        key: String,
+        /// Comma-separated list of field names or IDs to show
+        #[arg(long)]
+        fields: Option<String>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Set fields on an issue
+    Edit {
+        /// Issue key
+        key: String,
+        /// Set a field by name or ID (repeatable, e.g. "Story Points=3")
+        #[arg(
+            long = "set",
+            value_name = "FIELD=VALUE",
+            required_unless_present = "file"
+        )]
+        set: Vec<String>,
+        /// YAML or JSON file mapping field names or IDs to values
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+    /// List the transitions available on an issue and the fields they require
+    Transitions {
+        /// Issue key
+        key: String,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Delete an issue, after typing its key to confirm
+    Delete {
+        /// Issue key
+        key: String,
+        /// Also delete the issue's subtasks
+        #[arg(long)]
+        subtasks: bool,
+        /// Issue key, repeated to confirm without a prompt
+        #[arg(long)]
+        confirm: Option<String>,
+    },
+}
+
+/// Execute the jira issue command.
+pub fn run(
+    cmd: &IssueCmd,
+    dry_run: bool,
+    pretty: bool,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+    let client = create_jira_client()?;
+
+    match &cmd.operation {
+        IssueOp::Create {
+            project,
+            issue_type,
+            summary,
+            description,
+            labels,
+            assignee,
+            priority,
+            parent,
+            set,
+            file,
+            format,
+        } => {
+            let mut assignments = read_assignments(set, file.as_deref())?;
+            // Flags come last so they win over the same fields in the file.
+            let flags = [
+                ("summary", summary),
+                ("description", description),
+                ("labels", labels),
+                ("assignee", assignee),
+                ("priority", priority),
+                ("parent", parent),
+            ];
+            for (name, value) in flags {
+                if let Some(value) = value {
+                    assignments.push((name.to_string(), json!(value)));
+                }
+            }
+
+            // The project and issue type pick the create screen, so they are
+            // taken out before the other fields are checked against it.
+            let registry = client.field_registry()?;
+            let (mut file_project, mut file_type) = (None, None);
+            assignments.retain(|(name, value)| {
+                match registry.get(name).map(|f| f.id.as_str()) {
+                    Some("project") => file_project = Some(display_value(value)),
+                    Some("issuetype") => file_type = Some(display_value(value)),
+                    _ => return true,
+                }
+                false
+            });
+            let project = project
+                .clone()
+                .or(file_project)
+                .ok_or("No project: pass --project or set `project` in the file")?;
+            let issue_type = issue_type
+                .clone()
+                .or(file_type)
+                .unwrap_or_else(|| "Task".to_string());
+
+            if verbose {
+                eprintln!("Fetching create screen of {issue_type} in {project}");
+            }
+            let (type_id, meta) = client.get_create_meta(&project, &issue_type)?;
+            let mut fields = resolve_fields(&client, &assignments, Some((&meta, "create")))?;
+            fields.insert("project".to_string(), json!({ "key": project }));
+            fields.insert("issuetype".to_string(), json!({ "id": type_id }));
+
+            let mut missing: Vec<&str> = meta
+                .iter()
+                .filter(|(id, f)| f.required && !f.has_default_value && !fields.contains_key(*id))
+                .map(|(_, f)| f.name.as_str())
+                .collect();
+            if !missing.is_empty() {
+                missing.sort();
+                return Err(format!(
+                    "{issue_type} in {project} requires {}; pass them with --set",
+                    missing.join(", ")
+                )
+                .into());
+            }
+
+            if verbose {
+                eprintln!("Creating issue with {} fields", fields.len());
+            }
+            if dry_run {
+                println!("DRY RUN: Would create issue with fields:");
+                print_json(&fields, true)?;
+                return Ok(());
+            }
+
+            let created = client.create_issue(&fields)?;
+            match format {
+                OutputFormat::Json => print_json(&created, pretty)?,
+                OutputFormat::Table => println!("Created {}", created.key),
+            }
+        }
+        IssueOp::Get {
+            key,
+            fields,
+            format,
+        } => {
+            let registry = client.field_registry()?;
+            let field_ids = match fields {
+                Some(names) => names
+                    .split(',')
+                    .map(|name| registry.resolve(name.trim()).map(|f| f.id.clone()))
+                    .collect::<Result<Vec<_>, _>>()?,
+                None => Vec::new(),
+            };
+            if verbose {
+                eprintln!("Fetching issue {key}");
+            }
+
+            let issue = client.get_issue(key, &field_ids, &["names".to_string()])?;
+            match format {
+                OutputFormat::Json => print_json(&issue, pretty)?,
+                OutputFormat::Table => print_issue(&issue, &field_ids, registry),
+            }
+        }
+        IssueOp::Edit { key, set, file } => {
+            let assignments = read_assignments(set, file.as_deref())?;
+            let meta = client.get_edit_meta(key)?;
+            let fields = resolve_fields(&client, &assignments, Some((&meta, "edit")))?;
+            if dry_run {
+                println!("DRY RUN: Would update {key} with fields:");
+                print_json(&fields, true)?;
+                return Ok(());
+            }
+
+            client.update_issue(key, &fields)?;
+            println!("Updated {key} ({} fields)", fields.len());
+        }
+        IssueOp::Transitions { key, format } => {
+            let transitions = client.get_transitions(key)?;
+            match format {
+                OutputFormat::Json => print_json(&transitions, pretty)?,
+                OutputFormat::Table => {
+                    if transitions.is_empty() {
+                        println!("No transitions available.");
+                    }
+                    for transition in &transitions {
+                        let mut required: Vec<&str> = transition
+                            .fields
+                            .values()
+                            .filter(|f| f.required && !f.has_default_value)
+                            .map(|f| f.name.as_str())
+                            .collect();
+                        required.sort();
+                        print!("{:<24} -> {}", transition.name, transition.to.name);
+                        if !required.is_empty() {
+                            print!(" (requires {})", required.join(", "));
+                        }
+                        println!();
+                    }
+                }
+            }
+        }
+        IssueOp::Delete {
+            key,
+            subtasks,
+            confirm,
+        } => {
+            let issue = client.get_issue(key, &["summary".to_string()], &[])?;
+            let summary = issue.fields.summary.unwrap_or_default();
+            if dry_run {
+                println!("DRY RUN: Would delete issue {} '{summary}'", issue.key);
+                return Ok(());
+            }
+
+            let typed = match confirm {
+                Some(confirm) => confirm.clone(),
+                None => {
+                    eprint!(
+                        "Deleting issue {} '{summary}' cannot be undone.\nType the issue key to confirm: ",
+                        issue.key
+                    );
+                    io::stderr().flush()?;
+                    let mut line = String::new();
+                    io::stdin().lock().read_line(&mut line)?;
+                    line.trim().to_string()
+                }
+            };
+            if typed != issue.key {
+                return Err(format!(
+                    "Confirmation '{typed}' does not match issue key {}; nothing deleted",
+                    issue.key
+                )
+                .into());
+            }
+
+            client.delete_issue(&issue.key, *subtasks)?;
+            println!("Deleted issue {} '{summary}'", issue.key);
+        }
+    }
+
+    Ok(())
+}
+
+/// Read field assignments from a YAML file and `--set` flags, in that order.
+///
+/// Values from `--set` are kept as strings for [`resolve_fields`] to shape.
+pub(crate) fn read_assignments(
+    set: &[String],
+    file: Option<&Path>,
+) -> Result<Vec<(String, Value)>, Box<dyn Error>> {
+    let mut assignments = Vec::new();
+
+    if let Some(path) = file {
+        let text = fs::read_to_string(path)?;
+        let values: Map<String, Value> = serde_yaml::from_str(&text)
+            .map_err(|e| format!("Invalid field file {}: {e}", path.display()))?;
+        assignments.extend(values);
+    }
+
+    for assignment in set {
+        let (name, raw) = assignment
+            .split_once('=')
+            .ok_or_else(|| format!("Expected FIELD=VALUE, got '{assignment}'"))?;
+        assignments.push((name.trim().to_string(), json!(raw.trim())));
+    }
+
+    Ok(assignments)
+}
+
+/// Resolve field names to IDs and validate the values against their schema.
+///
+/// With `screen`, every field must be on that screen (named for errors, e.g.
+/// "create") and values are checked against its allowed values.
+pub(crate) fn resolve_fields(
+    client: &JiraClient,
+    assignments: &[(String, Value)],
+    screen: Option<(&HashMap<String, JiraFieldMeta>, &str)>,
+) -> Result<Map<String, Value>, Box<dyn Error>> {
+    let registry = client.field_registry()?;
+    let mut fields = Map::new();
+    for (name, value) in assignments {
+        let field = registry.resolve(name)?;
+        let meta = match screen {
+            Some((meta, screen)) => Some(meta.get(&field.id).ok_or_else(|| {
+                format!(
+                    "Field '{}' ({}) is not on the {screen} screen",
+                    field.name, field.id
+                )
+            })?),
+            None => None,
+        };
+        fields.insert(field.id.clone(), client.field_value(field, value, meta)?);
+    }
+    Ok(fields)
+}
+
+/// Print an issue for humans.
+///
+/// Fields asked for with `--fields` are shown by display name; without them,
+/// every custom field with a value is shown.
+fn print_issue(issue: &JiraIssue, field_ids: &[String], registry: &FieldRegistry) {
+    let fields = &issue.fields;
+    println!(
+        "{}  {}",
+        issue.key,
+        fields.summary.as_deref().unwrap_or_default()
+    );
+
+    let mut rows: Vec<(&str, String)> = Vec::new();
+    if let Some(status) = &fields.status {
+        rows.push(("Status", status.name.clone()));
+    }
+    if let Some(issue_type) = &fields.issue_type {
+        rows.push(("Type", issue_type.name.clone()));
+    }
+    if let Some(priority) = &fields.priority {
+        rows.push(("Priority", priority.name.clone()));
+    }
+    if let Some(assignee) = &fields.assignee {
+        rows.push(("Assignee", user_name(assignee)));
+    }
+    if let Some(reporter) = &fields.reporter {
+        rows.push(("Reporter", user_name(reporter)));
+    }
+    if let Some(labels) = fields.labels.as_ref().filter(|l| !l.is_empty()) {
+        rows.push(("Labels", labels.join(", ")));
+    }
+    if let Some(parent) = &fields.parent {
+        rows.push(("Parent", parent.key.clone()));
+    }
+    if let Some(created) = &fields.created {
+        rows.push(("Created", created.clone()));
+    }
+    if let Some(updated) = &fields.updated {
+        rows.push(("Updated", updated.clone()));
+    }
+    for (name, value) in &rows {
+        println!("  {:<10} {value}", format!("{name}:"));
+    }
+
+    let mut extra: Vec<&String> = if field_ids.is_empty() {
+        fields
+            .other
+            .keys()
+            .filter(|id| id.starts_with("customfield_"))
+            .collect()
+    } else {
+        field_ids.iter().collect()
+    };
+    extra.sort_by_key(|id| registry.name(id));
+    for id in extra {
+        let Some(value) = fields.other.get(id).filter(|v| !is_empty(v)) else {
+            continue;
+        };
+        println!("  {}: {}", registry.name(id), display_value(value));
+    }
+
+    if let Some(description) = fields.description.as_ref().filter(|d| !d.is_null()) {
+        println!();
+        for line in adf_to_markdown(description).lines() {
+            println!("  {line}");
+        }
+    }
+}
+
+/// Whether a field value has nothing worth showing.
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Array(items) => items.is_empty(),
+        Value::String(s) => s.is_empty(),
+        _ => false,
+    }
+}
+
+/// Display name of a user, falling back to the account ID.
+pub(crate) fn user_name(user: &JiraUser) -> String {
+    user.display_name
+        .clone()
+        .or_else(|| user.account_id.clone())
+        .unwrap_or_default()
+}
+
+/// Render a field value on one line.
+pub(crate) fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items
+            .iter()
+            .map(display_value)
+            .collect::<Vec<_>>()
+            .join(", "),
+        Value::Object(map) => ["displayName", "name", "value", "key"]
+            .iter()
+            .find_map(|k| map.get(*k).and_then(Value::as_str))
+            .map_or_else(|| value.to_string(), str::to_string),
+        other => other.to_string(),
+    }
+}
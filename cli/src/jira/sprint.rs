@@ -0,0 +1,651 @@
+//! `jira sprint`: list boards and sprints, move issues between sprints, start
+//! and close sprints, and report committed vs completed story points.
+//!
+//! The story point field is taken from `--points-field`, then the
+//! `JIRA_STORY_POINTS_FIELD` environment variable, then the estimation field
+//! configured on the sprint's board.
+
+use crate::create_jira_client;
+use crate::jira::issue::user_name;
+use crate::output::{print_json, OutputFormat};
+use chrono::{DateTime, Duration, FixedOffset, Local, NaiveDate, NaiveTime, SecondsFormat, Utc};
+use clap::{Args, Subcommand, ValueEnum};
+use nix_rust_template::{JiraBoard, JiraClient, JiraIssue, JiraSearchOptions, JiraSprint};
+use serde::Serialize;
+use serde_json::Value;
+use std::error::Error;
+
+/// Environment variable naming the story point field.
+const STORY_POINTS_ENV: &str = "JIRA_STORY_POINTS_FIELD";
+
+/// Date-time format Jira uses for issue timestamps.
+const JIRA_DATETIME: &str = "%Y-%m-%dT%H:%M:%S%.3f%z";
+
+/// Custom field type of the Sprint field, whose ID differs between sites.
+const SPRINT_FIELD_TYPE: &str = "com.pyxis.greenhopper.jira:gh-sprint";
+
+/// Length of a sprint started without an end date or planned end.
+const DEFAULT_SPRINT_DAYS: i64 = 14;
+
+#[derive(Args, Debug)]
+pub struct SprintCmd {
+    #[command(subcommand)]
+    pub operation: SprintOp,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SprintOp {
+    /// List boards
+    Boards {
+        /// Only boards of this project
+        #[arg(long)]
+        project: Option<String>,
+        /// Only boards whose name contains this text
+        #[arg(long)]
+        name: Option<String>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// List the sprints of a board
+    List {
+        /// Board ID or name
+        board: String,
+        /// Only sprints in these states (comma-separated)
+        #[arg(long, value_enum, value_delimiter = ',')]
+        state: Vec<SprintState>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// List the issues of a sprint
+    Issues {
+        /// Sprint ID
+        sprint: u64,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Move every issue matching a JQL query to a sprint or the backlog
+    Move {
+        /// JQL query selecting the issues to move
+        jql: String,
+        /// Sprint ID, or "backlog"
+        target: String,
+    },
+    /// Start a future sprint
+    Start {
+        /// Sprint ID
+        sprint: u64,
+        /// Start date (2024-03-01) or RFC 3339 timestamp; defaults to the
+        /// planned start, or now
+        #[arg(long)]
+        start: Option<String>,
+        /// End date (2024-03-15) or RFC 3339 timestamp; defaults to the
+        /// planned end, or two weeks after the start
+        #[arg(long)]
+        end: Option<String>,
+    },
+    /// Close an active sprint
+    Close {
+        /// Sprint ID
+        sprint: u64,
+    },
+    /// Summarize committed vs completed story points of a sprint
+    ///
+    /// Issues present when the sprint started count as committed; issues
+    /// added later count as added. Jira cannot search for issues that left a
+    /// sprint, so issues removed from the sprint are not counted.
+    Report {
+        /// Sprint ID
+        sprint: u64,
+        /// Story point field name or ID
+        #[arg(long)]
+        points_field: Option<String>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+}
+
+/// State of a sprint.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SprintState {
+    Future,
+    Active,
+    Closed,
+}
+
+impl SprintState {
+    fn as_str(self) -> &'static str {
+        match self {
+            SprintState::Future => "future",
+            SprintState::Active => "active",
+            SprintState::Closed => "closed",
+        }
+    }
+}
+
+/// A sprint issue, for listing.
+#[derive(Debug, Serialize)]
+struct IssueRow {
+    key: String,
+    summary: String,
+    status: String,
+    assignee: String,
+}
+
+/// Issue counts and story points of one part of the sprint report.
+#[derive(Debug, Default, Serialize)]
+struct Tally {
+    issues: usize,
+    points: f64,
+}
+
+impl Tally {
+    fn add(&mut self, points: f64) {
+        self.issues += 1;
+        self.points += points;
+    }
+}
+
+/// Committed vs completed work of a sprint.
+#[derive(Debug, Serialize)]
+struct SprintReport {
+    sprint: String,
+    state: String,
+    points_field: String,
+    /// Issues in the sprint when it started
+    committed: Tally,
+    /// Issues added after the sprint started
+    added: Tally,
+    /// Committed issues that were completed
+    completed_committed: Tally,
+    /// All completed issues
+    completed: Tally,
+    /// Issues not completed
+    not_completed: Tally,
+    /// Completed committed points as a percentage of committed points
+    completion_percent: Option<f64>,
+}
+
+/// Execute the jira sprint command.
+pub fn run(
+    cmd: &SprintCmd,
+    dry_run: bool,
+    pretty: bool,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+    let client = create_jira_client()?;
+
+    match &cmd.operation {
+        SprintOp::Boards {
+            project,
+            name,
+            format,
+        } => {
+            let boards = client.get_boards(project.as_deref(), name.as_deref())?;
+            match format {
+                OutputFormat::Json => print_json(&boards, pretty)?,
+                OutputFormat::Table => {
+                    if boards.is_empty() {
+                        println!("No boards found.");
+                    }
+                    for board in &boards {
+                        let project = board
+                            .location
+                            .as_ref()
+                            .and_then(|l| l.project_key.as_deref())
+                            .unwrap_or_default();
+                        println!(
+                            "{:<8} {:<8} {:<10} {}",
+                            board.id, board.board_type, project, board.name
+                        );
+                    }
+                }
+            }
+        }
+        SprintOp::List {
+            board,
+            state,
+            format,
+        } => {
+            let board = find_board(&client, board)?;
+            if verbose {
+                eprintln!("Listing sprints of board {} ({})", board.name, board.id);
+            }
+            let states: Vec<&str> = state.iter().map(|s| s.as_str()).collect();
+            let sprints = client.get_sprints(board.id, &states)?;
+            match format {
+                OutputFormat::Json => print_json(&sprints, pretty)?,
+                OutputFormat::Table => {
+                    if sprints.is_empty() {
+                        println!("No sprints found on board {}.", board.name);
+                    }
+                    for sprint in &sprints {
+                        println!(
+                            "{:<8} {:<8} {:<10} {:<10} {}",
+                            sprint.id,
+                            sprint.state,
+                            short_date(sprint.start_date.as_deref()),
+                            short_date(sprint.end_date.as_deref()),
+                            sprint.name
+                        );
+                    }
+                }
+            }
+        }
+        SprintOp::Issues { sprint, format } => {
+            let options = JiraSearchOptions {
+                fields: ["summary", "status", "assignee"]
+                    .map(str::to_string)
+                    .to_vec(),
+                ..JiraSearchOptions::default()
+            };
+            let rows: Vec<IssueRow> = client
+                .get_sprint_issues(*sprint, &options)?
+                .iter()
+                .map(|issue| IssueRow {
+                    key: issue.key.clone(),
+                    summary: issue.fields.summary.clone().unwrap_or_default(),
+                    status: issue
+                        .fields
+                        .status
+                        .as_ref()
+                        .map(|s| s.name.clone())
+                        .unwrap_or_default(),
+                    assignee: issue
+                        .fields
+                        .assignee
+                        .as_ref()
+                        .map(user_name)
+                        .unwrap_or_default(),
+                })
+                .collect();
+            match format {
+                OutputFormat::Json => print_json(&rows, pretty)?,
+                OutputFormat::Table => {
+                    if rows.is_empty() {
+                        println!("Sprint {sprint} has no issues.");
+                    }
+                    for row in &rows {
+                        println!(
+                            "{:<12} {:<16} {:<24} {}",
+                            row.key, row.status, row.assignee, row.summary
+                        );
+                    }
+                }
+            }
+        }
+        SprintOp::Move { jql, target } => {
+            let sprint_id = if target.eq_ignore_ascii_case("backlog") {
+                None
+            } else {
+                Some(target.parse::<u64>().map_err(|_| {
+                    format!("Invalid target '{target}': expected a sprint ID or \"backlog\"")
+                })?)
+            };
+            let target = match sprint_id {
+                Some(id) => format!("sprint {}", client.get_sprint(id)?.name),
+                None => "the backlog".to_string(),
+            };
+
+            let options = JiraSearchOptions {
+                fields: vec!["summary".to_string()],
+                ..JiraSearchOptions::default()
+            };
+            let issues = client.search_issues(jql, &options)?;
+            let keys: Vec<&str> = issues.iter().map(|i| i.key.as_str()).collect();
+            if keys.is_empty() {
+                println!("No issues match '{jql}'.");
+                return Ok(());
+            }
+
+            if dry_run {
+                println!(
+                    "DRY RUN: Would move {} issues to {target}: {}",
+                    keys.len(),
+                    keys.join(", ")
+                );
+                return Ok(());
+            }
+
+            client.move_issues_to_sprint(sprint_id, &keys)?;
+            println!("Moved {} issues to {target}", keys.len());
+        }
+        SprintOp::Start { sprint, start, end } => {
+            let current = client.get_sprint(*sprint)?;
+            if current.state != "future" {
+                return Err(format!(
+                    "Sprint {} is {}; only future sprints can be started",
+                    current.name, current.state
+                )
+                .into());
+            }
+            let start = match start {
+                Some(start) => parse_date("--start", start)?,
+                None => current
+                    .start_date
+                    .as_deref()
+                    .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+                    .map_or_else(Utc::now, |d| d.with_timezone(&Utc)),
+            };
+            let end = match end {
+                Some(end) => parse_date("--end", end)?,
+                None => current
+                    .end_date
+                    .as_deref()
+                    .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+                    .map(|d| d.with_timezone(&Utc))
+                    .filter(|d| *d > start)
+                    .unwrap_or(start + Duration::days(DEFAULT_SPRINT_DAYS)),
+            };
+            if end <= start {
+                return Err("The sprint must end after it starts".into());
+            }
+
+            if dry_run {
+                println!(
+                    "DRY RUN: Would start sprint {} from {} to {}",
+                    current.name,
+                    start.format("%Y-%m-%d %H:%M"),
+                    end.format("%Y-%m-%d %H:%M")
+                );
+                return Ok(());
+            }
+
+            let started = client.start_sprint(
+                *sprint,
+                &start.to_rfc3339_opts(SecondsFormat::Millis, true),
+                &end.to_rfc3339_opts(SecondsFormat::Millis, true),
+            )?;
+            println!(
+                "Started sprint {} (ends {})",
+                started.name,
+                short_date(started.end_date.as_deref())
+            );
+        }
+        SprintOp::Close { sprint } => {
+            let current = client.get_sprint(*sprint)?;
+            if current.state != "active" {
+                return Err(format!(
+                    "Sprint {} is {}; only active sprints can be closed",
+                    current.name, current.state
+                )
+                .into());
+            }
+
+            if dry_run {
+                println!("DRY RUN: Would close sprint {}", current.name);
+                return Ok(());
+            }
+
+            client.close_sprint(*sprint)?;
+            println!("Closed sprint {}", current.name);
+        }
+        SprintOp::Report {
+            sprint,
+            points_field,
+            format,
+        } => {
+            let sprint = client.get_sprint(*sprint)?;
+            let (field_id, field_name) = points_field_for(&client, &sprint, points_field)?;
+            if verbose {
+                eprintln!("Reading issues of sprint {} ({field_name})", sprint.name);
+            }
+
+            let options = JiraSearchOptions {
+                fields: ["summary", "status", "resolutiondate", field_id.as_str()]
+                    .map(str::to_string)
+                    .to_vec(),
+                expand: vec!["changelog".to_string()],
+                ..JiraSearchOptions::default()
+            };
+            let issues = client.get_sprint_issues(sprint.id, &options)?;
+            let sprint_field = client
+                .field_registry()?
+                .fields()
+                .iter()
+                .find(|f| {
+                    f.schema.as_ref().and_then(|s| s.custom.as_deref()) == Some(SPRINT_FIELD_TYPE)
+                })
+                .map(|f| f.id.clone())
+                .ok_or("No Sprint field found on this Jira site")?;
+            let report = sprint_report(&sprint, &issues, &sprint_field, &field_id, field_name);
+            match format {
+                OutputFormat::Json => print_json(&report, pretty)?,
+                OutputFormat::Table => print_report(&report),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Find a board by ID or by name.
+fn find_board(client: &JiraClient, board: &str) -> Result<JiraBoard, Box<dyn Error>> {
+    if let Ok(id) = board.parse::<u64>() {
+        return Ok(client.get_board(id)?);
+    }
+
+    let boards = client.get_boards(None, Some(board))?;
+    if let Some(exact) = boards.iter().find(|b| b.name.eq_ignore_ascii_case(board)) {
+        return Ok(exact.clone());
+    }
+    match boards.as_slice() {
+        [] => Err(format!("No board named '{board}'").into()),
+        [only] => Ok(only.clone()),
+        _ => {
+            let names: Vec<String> = boards
+                .iter()
+                .map(|b| format!("{} ({})", b.name, b.id))
+                .collect();
+            Err(format!(
+                "Board name '{board}' is ambiguous: {}; use a board ID",
+                names.join(", ")
+            )
+            .into())
+        }
+    }
+}
+
+/// Story point field ID and name for a sprint.
+fn points_field_for(
+    client: &JiraClient,
+    sprint: &JiraSprint,
+    points_field: &Option<String>,
+) -> Result<(String, String), Box<dyn Error>> {
+    let configured = points_field
+        .clone()
+        .or_else(|| std::env::var(STORY_POINTS_ENV).ok())
+        .filter(|f| !f.is_empty());
+    if let Some(name) = configured {
+        let field = client.field_registry()?.resolve(&name)?;
+        return Ok((field.id.clone(), field.name.clone()));
+    }
+
+    let estimation = match sprint.origin_board_id {
+        Some(board_id) => client.get_board_configuration(board_id)?.estimation,
+        None => None,
+    };
+    match estimation.and_then(|e| e.field) {
+        Some(field) => {
+            let name = field.display_name.unwrap_or_else(|| field.field_id.clone());
+            Ok((field.field_id, name))
+        }
+        None => Err(format!(
+            "No story point field configured for sprint {}; use --points-field or set {STORY_POINTS_ENV}",
+            sprint.name
+        )
+        .into()),
+    }
+}
+
+/// Build the committed vs completed report of a sprint.
+fn sprint_report(
+    sprint: &JiraSprint,
+    issues: &[JiraIssue],
+    sprint_field: &str,
+    field_id: &str,
+    field_name: String,
+) -> SprintReport {
+    let start = sprint.start_date.as_deref().and_then(parse_jira_date);
+    let complete = sprint.complete_date.as_deref().and_then(parse_jira_date);
+
+    let mut report = SprintReport {
+        sprint: sprint.name.clone(),
+        state: sprint.state.clone(),
+        points_field: field_name,
+        committed: Tally::default(),
+        added: Tally::default(),
+        completed_committed: Tally::default(),
+        completed: Tally::default(),
+        not_completed: Tally::default(),
+        completion_percent: None,
+    };
+
+    for issue in issues {
+        let points = issue
+            .fields
+            .other
+            .get(field_id)
+            .and_then(Value::as_f64)
+            .unwrap_or(0.0);
+        let added = start.is_some_and(|start| {
+            added_to_sprint(issue, sprint_field, sprint.id).is_some_and(|added| added > start)
+        });
+        let done = issue
+            .fields
+            .status
+            .as_ref()
+            .and_then(|s| s.category.as_ref())
+            .is_some_and(|c| c.key == "done");
+        // Issues resolved after a closed sprint ended were finished in a later sprint
+        let resolved_in_time = match complete {
+            Some(complete) => issue
+                .fields
+                .other
+                .get("resolutiondate")
+                .and_then(Value::as_str)
+                .and_then(parse_jira_date)
+                .is_none_or(|resolved| resolved <= complete),
+            None => true,
+        };
+        let completed = done && resolved_in_time;
+
+        if added {
+            report.added.add(points);
+        } else {
+            report.committed.add(points);
+        }
+        if completed {
+            report.completed.add(points);
+            if !added {
+                report.completed_committed.add(points);
+            }
+        } else {
+            report.not_completed.add(points);
+        }
+    }
+
+    if report.committed.points > 0.0 {
+        let percent = report.completed_committed.points / report.committed.points * 100.0;
+        report.completion_percent = Some((percent * 10.0).round() / 10.0);
+    }
+    report
+}
+
+/// When an issue was last added to a sprint, according to its changelog.
+fn added_to_sprint(
+    issue: &JiraIssue,
+    sprint_field: &str,
+    sprint_id: u64,
+) -> Option<DateTime<FixedOffset>> {
+    let histories = issue.changelog.as_ref()?.get("histories")?.as_array()?;
+    let in_sprint = |ids: Option<&Value>| {
+        ids.and_then(Value::as_str).is_some_and(|ids| {
+            ids.split(',')
+                .any(|id| id.trim().parse::<u64>().ok() == Some(sprint_id))
+        })
+    };
+
+    histories
+        .iter()
+        .filter(|history| {
+            history
+                .get("items")
+                .and_then(Value::as_array)
+                .is_some_and(|items| {
+                    items.iter().any(|item| {
+                        item.get("fieldId").and_then(Value::as_str) == Some(sprint_field)
+                            && in_sprint(item.get("to"))
+                            && !in_sprint(item.get("from"))
+                    })
+                })
+        })
+        .filter_map(|history| history.get("created")?.as_str().and_then(parse_jira_date))
+        .max()
+}
+
+/// Print the sprint report as a table.
+fn print_report(report: &SprintReport) {
+    println!("{} ({})", report.sprint, report.state);
+    println!("Points field: {}", report.points_field);
+    println!();
+    let rows = [
+        ("Committed", &report.committed),
+        ("Added", &report.added),
+        ("Completed (committed)", &report.completed_committed),
+        ("Completed", &report.completed),
+        ("Not completed", &report.not_completed),
+    ];
+    for (label, tally) in rows {
+        println!(
+            "{label:<24} {:>5} issues {:>8} points",
+            tally.issues,
+            format_points(tally.points)
+        );
+    }
+    if let Some(percent) = report.completion_percent {
+        println!();
+        println!("Completion: {percent}% of committed points");
+    }
+    println!("Issues removed from the sprint are not included.");
+}
+
+/// Format story points without a trailing ".0".
+fn format_points(points: f64) -> String {
+    if points.fract() == 0.0 {
+        format!("{points:.0}")
+    } else {
+        format!("{points:.1}")
+    }
+}
+
+/// The date part of a Jira timestamp.
+fn short_date(date: Option<&str>) -> &str {
+    date.map(|d| d.get(..10).unwrap_or(d)).unwrap_or("-")
+}
+
+/// Parse a Jira timestamp, in RFC 3339 or Jira's own offset format.
+fn parse_jira_date(date: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(date)
+        .or_else(|_| DateTime::parse_from_str(date, JIRA_DATETIME))
+        .ok()
+}
+
+/// Parse a sprint date: a date (local midnight) or an RFC 3339 timestamp.
+fn parse_date(flag: &str, date: &str) -> Result<DateTime<Utc>, Box<dyn Error>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(date) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| {
+            d.and_time(NaiveTime::MIN)
+                .and_local_timezone(Local)
+                .earliest()
+        })
+        .map(|time| time.with_timezone(&Utc))
+        .ok_or_else(|| format!("Invalid {flag} '{date}': expected YYYY-MM-DD or RFC 3339").into())
+}
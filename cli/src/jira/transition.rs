@@ -0,0 +1,257 @@
+//! `jira transition`: move every issue matching a JQL query to a status.
+
+use crate::create_jira_client;
+use crate::jira::issue::read_assignments;
+use crate::output::{print_json, OutputFormat};
+use clap::Args;
+use nix_rust_template::{
+    JiraClient, JiraError, JiraField, JiraIssue, JiraSearchOptions, JiraTransition,
+};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::error::Error;
+use std::path::PathBuf;
+
+#[derive(Args, Debug)]
+pub struct TransitionArgs {
+    /// JQL query selecting the issues to move
+    pub jql: String,
+    /// Target status name, or transition name
+    pub status: String,
+    /// Resolution name to set; issues whose transition screen has no
+    /// Resolution field are skipped
+    #[arg(long)]
+    pub resolution: Option<String>,
+    /// Set a screen field by name or ID (repeatable, e.g. "Fix versions=1.2")
+    #[arg(long = "set", value_name = "FIELD=VALUE")]
+    pub set: Vec<String>,
+    /// YAML or JSON file mapping screen field names or IDs to values
+    #[arg(long)]
+    pub file: Option<PathBuf>,
+    /// Output format
+    #[arg(long, value_enum, default_value_t)]
+    pub format: OutputFormat,
+}
+
+/// What happened to one issue.
+#[derive(Debug, Serialize)]
+#[serde(tag = "outcome", rename_all = "lowercase")]
+enum Outcome {
+    /// The issue was moved, or would be in a dry run
+    Transitioned { transition: String },
+    /// The issue was left alone
+    Skipped { reason: String },
+    /// The transition was attempted and Jira rejected it
+    Failed { error: String },
+}
+
+/// Outcome for one issue, for the report.
+#[derive(Debug, Serialize)]
+struct IssueOutcome {
+    key: String,
+    summary: String,
+    from: String,
+    #[serde(flatten)]
+    outcome: Outcome,
+}
+
+/// Execute the jira transition command.
+pub fn run(
+    args: &TransitionArgs,
+    dry_run: bool,
+    pretty: bool,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+    let client = create_jira_client()?;
+
+    let mut assignments = read_assignments(&args.set, args.file.as_deref())?;
+    if let Some(resolution) = &args.resolution {
+        assignments.push(("resolution".to_string(), json!(resolution)));
+    }
+    // Names are resolved once up front; values are checked per transition
+    // screen, since allowed values can differ between workflows.
+    let fields = if assignments.is_empty() {
+        Vec::new()
+    } else {
+        let registry = client.field_registry()?;
+        assignments
+            .into_iter()
+            .map(|(name, value)| Ok((registry.resolve(&name)?, value)))
+            .collect::<Result<Vec<_>, JiraError>>()?
+    };
+
+    let options = JiraSearchOptions {
+        fields: vec!["summary".to_string(), "status".to_string()],
+        ..JiraSearchOptions::default()
+    };
+    let issues = client.search_issues(&args.jql, &options)?;
+    if verbose {
+        eprintln!(
+            "Moving {} issues matching '{}' to '{}'",
+            issues.len(),
+            args.jql,
+            args.status
+        );
+    }
+
+    let mut report = Vec::new();
+    for issue in &issues {
+        let from = issue
+            .fields
+            .status
+            .as_ref()
+            .map(|s| s.name.clone())
+            .unwrap_or_default();
+        let outcome = if from.eq_ignore_ascii_case(&args.status) {
+            Outcome::Skipped {
+                reason: format!("already in '{from}'"),
+            }
+        } else {
+            match client.get_transitions(&issue.key) {
+                Err(e) => Outcome::Failed {
+                    error: e.to_string(),
+                },
+                Ok(transitions) => match find_transition(&transitions, &args.status) {
+                    None => Outcome::Skipped {
+                        reason: format!(
+                            "no transition to '{}' from '{from}' (available: {})",
+                            args.status,
+                            available(&transitions)
+                        ),
+                    },
+                    Some(transition) => apply(&client, issue, transition, &fields, dry_run),
+                },
+            }
+        };
+        report.push(IssueOutcome {
+            key: issue.key.clone(),
+            summary: issue.fields.summary.clone().unwrap_or_default(),
+            from,
+            outcome,
+        });
+    }
+
+    match args.format {
+        OutputFormat::Json => print_json(&report, pretty)?,
+        OutputFormat::Table => print_report(&report, dry_run),
+    }
+    Ok(())
+}
+
+/// Find the transition to a status, matched by target status or transition name.
+fn find_transition<'a>(
+    transitions: &'a [JiraTransition],
+    status: &str,
+) -> Option<&'a JiraTransition> {
+    transitions
+        .iter()
+        .find(|t| t.to.name.eq_ignore_ascii_case(status))
+        .or_else(|| {
+            transitions
+                .iter()
+                .find(|t| t.name.eq_ignore_ascii_case(status))
+        })
+}
+
+/// Comma-separated target statuses of the available transitions.
+fn available(transitions: &[JiraTransition]) -> String {
+    if transitions.is_empty() {
+        return "none".to_string();
+    }
+    transitions
+        .iter()
+        .map(|t| format!("'{}'", t.to.name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Check the screen fields and perform the transition.
+fn apply(
+    client: &JiraClient,
+    issue: &JiraIssue,
+    transition: &JiraTransition,
+    fields: &[(&JiraField, Value)],
+    dry_run: bool,
+) -> Outcome {
+    let mut screen_fields = Map::new();
+    for (field, value) in fields {
+        // Jira rejects fields that are not on the transition screen, and
+        // transitioning without a value that was asked for is worse.
+        let Some(meta) = transition.fields.get(&field.id) else {
+            return Outcome::Skipped {
+                reason: format!(
+                    "'{}' is not on the '{}' screen",
+                    field.name, transition.name
+                ),
+            };
+        };
+        match client.field_value(field, value, Some(meta)) {
+            Ok(value) => {
+                screen_fields.insert(field.id.clone(), value);
+            }
+            Err(e) => {
+                return Outcome::Skipped {
+                    reason: e.to_string(),
+                }
+            }
+        }
+    }
+
+    let missing = transition.missing_fields(&screen_fields);
+    if !missing.is_empty() {
+        return Outcome::Skipped {
+            reason: format!(
+                "'{}' requires {}; pass them with --set",
+                transition.name,
+                missing.join(", ")
+            ),
+        };
+    }
+
+    if !dry_run {
+        if let Err(e) = client.transition_issue(&issue.key, &transition.id, &screen_fields) {
+            return Outcome::Failed {
+                error: e.to_string(),
+            };
+        }
+    }
+    Outcome::Transitioned {
+        transition: transition.name.clone(),
+    }
+}
+
+/// Print one line per issue followed by totals.
+fn print_report(report: &[IssueOutcome], dry_run: bool) {
+    if report.is_empty() {
+        println!("No issues found.");
+        return;
+    }
+
+    let (mut moved, mut skipped, mut failed) = (0, 0, 0);
+    for entry in report {
+        let result = match &entry.outcome {
+            Outcome::Transitioned { transition } => {
+                moved += 1;
+                if dry_run {
+                    format!("DRY RUN: Would apply '{transition}'")
+                } else {
+                    format!("applied '{transition}'")
+                }
+            }
+            Outcome::Skipped { reason } => {
+                skipped += 1;
+                format!("skipped: {reason}")
+            }
+            Outcome::Failed { error } => {
+                failed += 1;
+                format!("failed: {error}")
+            }
+        };
+        println!(
+            "{:<12} {:<16} {} ({})",
+            entry.key, entry.from, result, entry.summary
+        );
+    }
+    let verb = if dry_run { "would move" } else { "moved" };
+    println!("{moved} {verb}, {skipped} skipped, {failed} failed");
+}
@@ -0,0 +1,391 @@
+//! `jira worklog`: list and log time on an issue, and report logged time
+//! across the issues matching a JQL query.
+
+use crate::create_jira_client;
+use crate::jira::issue::user_name;
+use crate::output::{print_json, OutputFormat};
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use clap::{Args, Subcommand, ValueEnum};
+use humantime_serde::re::humantime;
+use nix_rust_template::{adf_to_markdown, markdown_to_adf, JiraNewWorklog, JiraSearchOptions};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::error::Error;
+
+/// Date-time format Jira uses for worklog start times.
+const JIRA_DATETIME: &str = "%Y-%m-%dT%H:%M:%S%.3f%z";
+
+#[derive(Args, Debug)]
+pub struct WorklogCmd {
+    #[command(subcommand)]
+    pub operation: WorklogOp,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum WorklogOp {
+    /// List the time logged on an issue
+    List {
+        /// Issue key
+        key: String,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Log time on an issue
+    Add {
+        /// Issue key
+        key: String,
+        /// Time spent in Jira format (e.g. "1h 30m" or "2d")
+        #[arg(long)]
+        time_spent: String,
+        /// When the work started: a date (2024-03-01), a local date and time
+        /// (2024-03-01T09:30) or an RFC 3339 timestamp; defaults to now
+        #[arg(long)]
+        started: Option<String>,
+        /// Comment, in Markdown
+        #[arg(long)]
+        comment: Option<String>,
+    },
+    /// Sum logged time per person and issue for issues matching a JQL query
+    Report {
+        /// JQL query selecting the issues to report on
+        jql: String,
+        /// Group the report by person or by issue
+        #[arg(long, value_enum, default_value_t)]
+        by: ReportGrouping,
+        /// Only count work started on or after a date (2024-03-01) or within
+        /// a duration before now (e.g. "7d")
+        #[arg(long)]
+        since: Option<String>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+}
+
+/// How the worklog report is grouped.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReportGrouping {
+    /// One group per person, listing the issues they logged time on
+    #[default]
+    User,
+    /// One group per issue, listing who logged time on it
+    Issue,
+}
+
+/// A worklog with its comment as Markdown, for JSON output.
+#[derive(Debug, Serialize)]
+struct WorklogRow {
+    id: String,
+    author: String,
+    started: Option<String>,
+    time_spent: Option<String>,
+    time_spent_seconds: u64,
+    comment: String,
+}
+
+/// Logged time for one person or issue, with its breakdown.
+#[derive(Debug, Default, Serialize)]
+struct ReportGroup {
+    /// Person's name or issue key
+    name: String,
+    /// Person's account ID, when grouped by person
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account_id: Option<String>,
+    /// Issue summary, when grouped by issue
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+    seconds: u64,
+    hours: f64,
+    entries: Vec<ReportEntry>,
+}
+
+/// Logged time for one issue of a person, or one person on an issue.
+#[derive(Debug, Serialize)]
+struct ReportEntry {
+    /// Issue key or person's name
+    name: String,
+    /// Person's account ID, when grouped by issue
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account_id: Option<String>,
+    /// Issue summary, when grouped by person
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+    seconds: u64,
+    hours: f64,
+}
+
+/// Execute the jira worklog command.
+pub fn run(
+    cmd: &WorklogCmd,
+    dry_run: bool,
+    pretty: bool,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+    let client = create_jira_client()?;
+
+    match &cmd.operation {
+        WorklogOp::List { key, format } => {
+            if verbose {
+                eprintln!("Fetching worklogs of {key}");
+            }
+            let rows: Vec<WorklogRow> = client
+                .get_worklogs(key, None)?
+                .into_iter()
+                .map(|worklog| WorklogRow {
+                    id: worklog.id,
+                    author: worklog.author.as_ref().map(user_name).unwrap_or_default(),
+                    started: worklog.started,
+                    time_spent: worklog.time_spent,
+                    time_spent_seconds: worklog.time_spent_seconds,
+                    comment: worklog
+                        .comment
+                        .as_ref()
+                        .map(adf_to_markdown)
+                        .unwrap_or_default(),
+                })
+                .collect();
+
+            match format {
+                OutputFormat::Json => print_json(&rows, pretty)?,
+                OutputFormat::Table => {
+                    if rows.is_empty() {
+                        println!("No time logged on {key}.");
+                        return Ok(());
+                    }
+                    for row in &rows {
+                        let comment = row.comment.lines().next().unwrap_or_default();
+                        println!(
+                            "{:<28} {:<24} {:>8}  {comment}",
+                            row.started.as_deref().unwrap_or_default(),
+                            row.author,
+                            format_seconds(row.time_spent_seconds)
+                        );
+                    }
+                    let total: u64 = rows.iter().map(|r| r.time_spent_seconds).sum();
+                    println!(
+                        "Total: {} in {} worklogs",
+                        format_seconds(total),
+                        rows.len()
+                    );
+                }
+            }
+        }
+        WorklogOp::Add {
+            key,
+            time_spent,
+            started,
+            comment,
+        } => {
+            let started = match started {
+                Some(started) => parse_started(started)?,
+                None => Local::now().fixed_offset(),
+            };
+            let worklog = JiraNewWorklog {
+                time_spent: time_spent.clone(),
+                started: Some(started.format(JIRA_DATETIME).to_string()),
+                comment: comment.as_deref().map(markdown_to_adf),
+            };
+
+            if dry_run {
+                println!(
+                    "DRY RUN: Would log {time_spent} on {key} starting {}",
+                    started.format("%Y-%m-%d %H:%M")
+                );
+                return Ok(());
+            }
+
+            let created = client.add_worklog(key, &worklog)?;
+            println!(
+                "Logged {} on {key} (worklog #{})",
+                created.time_spent.as_deref().unwrap_or(time_spent),
+                created.id
+            );
+        }
+        WorklogOp::Report {
+            jql,
+            by,
+            since,
+            format,
+        } => {
+            let since = since.as_deref().map(parse_since).transpose()?;
+            let options = JiraSearchOptions {
+                fields: vec!["summary".to_string()],
+                ..JiraSearchOptions::default()
+            };
+            let issues = client.search_issues(jql, &options)?;
+            if verbose {
+                eprintln!(
+                    "Reading worklogs of {} issues matching '{jql}'",
+                    issues.len()
+                );
+            }
+
+            // Seconds per (account ID, issue key); display names are only for
+            // output, since they are neither unique nor stable.
+            let mut logged: BTreeMap<(String, String), u64> = BTreeMap::new();
+            let mut people: BTreeMap<String, String> = BTreeMap::new();
+            let mut summaries: BTreeMap<String, String> = BTreeMap::new();
+            for issue in &issues {
+                let worklogs =
+                    client.get_worklogs(&issue.key, since.map(|s| s.timestamp_millis()))?;
+                for worklog in worklogs {
+                    let started = worklog
+                        .started
+                        .as_deref()
+                        .and_then(|s| DateTime::parse_from_str(s, JIRA_DATETIME).ok());
+                    if since.is_some_and(|since| started.is_some_and(|s| s < since)) {
+                        continue;
+                    }
+                    let author = worklog.author.as_ref();
+                    let account = author
+                        .and_then(|a| a.account_id.clone())
+                        .unwrap_or_else(|| "unknown".to_string());
+                    people.entry(account.clone()).or_insert_with(|| {
+                        author
+                            .map(user_name)
+                            .unwrap_or_else(|| "unknown".to_string())
+                    });
+                    *logged.entry((account, issue.key.clone())).or_default() +=
+                        worklog.time_spent_seconds;
+                }
+                summaries.insert(
+                    issue.key.clone(),
+                    issue.fields.summary.clone().unwrap_or_default(),
+                );
+            }
+
+            let groups = group_report(&logged, &people, &summaries, *by);
+            match format {
+                OutputFormat::Json => print_json(&groups, pretty)?,
+                OutputFormat::Table => print_report(&groups),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Group logged seconds per account and issue into report groups, naming
+/// accounts by their display names.
+fn group_report(
+    logged: &BTreeMap<(String, String), u64>,
+    people: &BTreeMap<String, String>,
+    summaries: &BTreeMap<String, String>,
+    by: ReportGrouping,
+) -> Vec<ReportGroup> {
+    let summary = |key: &str| summaries.get(key).cloned();
+    let name = |account: &str| people.get(account).cloned().unwrap_or_default();
+    let mut groups: BTreeMap<&str, ReportGroup> = BTreeMap::new();
+    for ((account, key), &seconds) in logged {
+        let by_user = by == ReportGrouping::User;
+        let group_id = if by_user { account } else { key };
+        let group = groups.entry(group_id).or_insert_with(|| ReportGroup {
+            name: if by_user { name(account) } else { key.clone() },
+            account_id: by_user.then(|| account.clone()),
+            summary: (!by_user).then(|| summary(key)).flatten(),
+            ..ReportGroup::default()
+        });
+        group.seconds += seconds;
+        group.entries.push(ReportEntry {
+            name: if by_user { key.clone() } else { name(account) },
+            account_id: (!by_user).then(|| account.clone()),
+            summary: by_user.then(|| summary(key)).flatten(),
+            seconds,
+            hours: hours(seconds),
+        });
+    }
+
+    let mut groups: Vec<ReportGroup> = groups
+        .into_values()
+        .map(|mut group| {
+            group.hours = hours(group.seconds);
+            group
+        })
+        .collect();
+    groups.sort_by(|a, b| a.name.cmp(&b.name));
+    groups
+}
+
+/// Print report groups with their entries and a grand total.
+fn print_report(groups: &[ReportGroup]) {
+    if groups.is_empty() {
+        println!("No time logged.");
+        return;
+    }
+
+    for group in groups {
+        match &group.summary {
+            Some(summary) => println!("{}  {summary}", group.name),
+            None => println!("{}", group.name),
+        }
+        for entry in &group.entries {
+            println!(
+                "  {:>8}  {:<24} {}",
+                format_seconds(entry.seconds),
+                entry.name,
+                entry.summary.as_deref().unwrap_or_default()
+            );
+        }
+        println!("  {:>8}  total", format_seconds(group.seconds));
+    }
+    let total: u64 = groups.iter().map(|g| g.seconds).sum();
+    println!("Total: {} ({:.2} h)", format_seconds(total), hours(total));
+}
+
+/// Seconds as hours rounded to two decimals.
+fn hours(seconds: u64) -> f64 {
+    (seconds as f64 / 36.0).round() / 100.0
+}
+
+/// Format seconds as hours and minutes (e.g. "3h 30m").
+fn format_seconds(seconds: u64) -> String {
+    let (h, m) = (seconds / 3600, seconds % 3600 / 60);
+    match (h, m) {
+        (0, m) => format!("{m}m"),
+        (h, 0) => format!("{h}h"),
+        (h, m) => format!("{h}h {m}m"),
+    }
+}
+
+/// Parse the start of a worklog: a date, a local date and time, or RFC 3339.
+fn parse_started(started: &str) -> Result<DateTime<FixedOffset>, Box<dyn Error>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(started) {
+        return Ok(time);
+    }
+    let local = ["%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(started, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(started, "%Y-%m-%d")
+                .ok()
+                .map(|date| date.and_time(NaiveTime::MIN))
+        })
+        .ok_or_else(|| {
+            format!(
+                "Invalid --started '{started}': expected YYYY-MM-DD, YYYY-MM-DDTHH:MM or RFC 3339"
+            )
+        })?;
+    local
+        .and_local_timezone(Local)
+        .earliest()
+        .map(|time| time.fixed_offset())
+        .ok_or_else(|| format!("Invalid --started '{started}': no such local time").into())
+}
+
+/// Parse `--since`: a date (local midnight) or a duration before now.
+fn parse_since(since: &str) -> Result<DateTime<Utc>, Box<dyn Error>> {
+    if let Ok(date) = NaiveDate::parse_from_str(since, "%Y-%m-%d") {
+        if let Some(time) = date
+            .and_time(NaiveTime::MIN)
+            .and_local_timezone(Local)
+            .earliest()
+        {
+            return Ok(time.with_timezone(&Utc));
+        }
+    }
+    let duration = humantime::parse_duration(since).map_err(|_| {
+        format!("Invalid --since '{since}': expected a date (YYYY-MM-DD) or a duration (e.g. 7d)")
+    })?;
+    Ok(Utc::now() - chrono::Duration::from_std(duration)?)
+}
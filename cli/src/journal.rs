@@ -0,0 +1,145 @@
+//! Local journal of label changes made by `ctag`, used for history and undo.
+//!
+//! Every page change is appended as one JSON line as soon as it is made, so
+//! an interrupted run still leaves an accurate record behind.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+
+/// Environment variable overriding the journal file location.
+const JOURNAL_ENV: &str = "ACLI_JOURNAL";
+
+/// Label change made to one page during a run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// ID of the run that made the change
+    pub run_id: String,
+    /// When the change was made
+    pub time: DateTime<Utc>,
+    /// Command line of the run
+    pub command: String,
+    /// Page ID
+    pub page_id: String,
+    /// Page title at the time of the change
+    pub title: String,
+    /// Labels before the change
+    pub before: Vec<String>,
+    /// Labels after the change
+    pub after: Vec<String>,
+}
+
+/// A run of a mutating command, writing its page changes to the journal.
+#[derive(Debug)]
+pub struct JournalRun {
+    path: PathBuf,
+    run_id: String,
+    command: String,
+}
+
+/// Changes grouped by the run that made them.
+#[derive(Debug, Serialize)]
+pub struct RunSummary {
+    /// Run ID
+    pub run_id: String,
+    /// When the first change was made
+    pub time: DateTime<Utc>,
+    /// Command line of the run
+    pub command: String,
+    /// Page changes in the order they were made
+    pub changes: Vec<JournalEntry>,
+}
+
+/// Location of the journal file.
+pub fn journal_path() -> Result<PathBuf, Box<dyn Error>> {
+    if let Ok(path) = std::env::var(JOURNAL_ENV) {
+        return Ok(PathBuf::from(path));
+    }
+    let dir = dirs::data_local_dir().ok_or("Could not determine a local data directory")?;
+    Ok(dir.join("acli").join("ctag-journal.jsonl"))
+}
+
+impl JournalRun {
+    /// Start a new run for the current command line.
+    pub fn start() -> Result<Self, Box<dyn Error>> {
+        let now = Utc::now();
+        let command = std::env::args().collect::<Vec<_>>().join(" ");
+        Ok(JournalRun {
+            path: journal_path()?,
+            run_id: format!("{}-{}", now.format("%Y%m%dT%H%M%SZ"), std::process::id()),
+            command,
+        })
+    }
+
+    /// ID of this run.
+    pub fn id(&self) -> &str {
+        &self.run_id
+    }
+
+    /// Append a page change; changes that leave the labels untouched are skipped.
+    pub fn record(
+        &self,
+        page_id: &str,
+        title: &str,
+        before: &[String],
+        after: &[String],
+    ) -> Result<(), Box<dyn Error>> {
+        let mut old = before.to_vec();
+        let mut new = after.to_vec();
+        old.sort();
+        new.sort();
+        if old == new {
+            return Ok(());
+        }
+
+        let entry = JournalEntry {
+            run_id: self.run_id.clone(),
+            time: Utc::now(),
+            command: self.command.clone(),
+            page_id: page_id.to_string(),
+            title: title.to_string(),
+            before: old,
+            after: new,
+        };
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+        Ok(())
+    }
+}
+
+/// Read all runs from the journal, oldest first.
+pub fn read_runs() -> Result<Vec<RunSummary>, Box<dyn Error>> {
+    let path = journal_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut runs: Vec<RunSummary> = Vec::new();
+    for line in BufReader::new(fs::File::open(&path)?).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: JournalEntry = serde_json::from_str(&line)?;
+        match runs.iter_mut().find(|r| r.run_id == entry.run_id) {
+            Some(run) => run.changes.push(entry),
+            None => runs.push(RunSummary {
+                run_id: entry.run_id.clone(),
+                time: entry.time,
+                command: entry.command.clone(),
+                changes: vec![entry],
+            }),
+        }
+    }
+    Ok(runs)
+}
@@ -0,0 +1,176 @@
+use crate::create_jira_client;
+use crate::labels::{
+    label_edits, parse_labels, parse_updates, print_plan, LabelChange, PlannedPage,
+};
+use clap::{Args, Subcommand};
+use nix_rust_template::{JiraClient, JiraIssue, JiraSearchOptions};
+use std::error::Error;
+
+/// jtag command: operate on Jira issue labels matched by a JQL query.
+///
+/// Intended usage examples:
+///   acli jtag list "project = OPS" "incident,postmortem"
+///   acli jtag add "project = OPS and type = Bug" "triage"
+///   acli jtag update "project = OPS" "howto:how-to,run-book:runbook"
+///   acli jtag remove "project = OPS" "obsolete"
+///
+/// Label changes are planned with the same logic as `ctag`, so `--dry-run`
+/// prints the same plan format for issues as it does for pages.
+#[derive(Args, Debug)]
+pub struct JtagCmd {
+    #[command(subcommand)]
+    pub operation: JtagOp,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum JtagOp {
+    /// List labels for issues matching the JQL query
+    List {
+        /// JQL query selecting issues to operate on
+        jql: String,
+        /// Optional comma-separated list of tags to highlight in the output
+        tags: Option<String>,
+    },
+    /// Add labels to issues matching the JQL query
+    Add {
+        /// JQL query selecting issues to operate on
+        jql: String,
+        /// Comma-separated list of labels to add (e.g., "foo,bar,baz")
+        tags: String,
+    },
+    /// Update labels on issues matching the JQL query
+    Update {
+        /// JQL query selecting issues to operate on
+        jql: String,
+        /// Comma-separated list of label updates in format "old:new,old2:new2" (e.g., "foo:bar,baz:foo")
+        tags: String,
+    },
+    /// Remove labels from issues matching the JQL query
+    Remove {
+        /// JQL query selecting issues to operate on
+        jql: String,
+        /// Comma-separated list of labels to remove (e.g., "foo,bar,baz")
+        tags: String,
+    },
+}
+
+/// Execute the jtag command.
+pub fn run(
+    cmd: &JtagCmd,
+    dry_run: bool,
+    _pretty: bool,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+    let (jql, change, verb) = match &cmd.operation {
+        JtagOp::List { jql, tags } => {
+            if verbose {
+                eprintln!("Listing issues matching: {jql}");
+            }
+            let highlight_list = tags.as_deref().map(parse_labels);
+            if dry_run {
+                println!("DRY RUN: Would list issues for JQL: {jql}");
+                if let Some(tags) = &highlight_list {
+                    println!("DRY RUN: Would highlight issues with tags: {tags:?}");
+                }
+                return Ok(());
+            }
+            let client = create_jira_client()?;
+            display_issues(&search(&client, jql)?, highlight_list.as_deref());
+            return Ok(());
+        }
+        JtagOp::Add { jql, tags } => (jql, LabelChange::Add(parse_labels(tags)), "add"),
+        JtagOp::Update { jql, tags } => (jql, LabelChange::Update(parse_updates(tags)?), "update"),
+        JtagOp::Remove { jql, tags } => (jql, LabelChange::Remove(parse_labels(tags)), "remove"),
+    };
+
+    if verbose {
+        eprintln!("Planning label {verb} on issues matching: {jql}");
+    }
+    let client = create_jira_client()?;
+    let issues = search(&client, jql)?;
+    if issues.is_empty() {
+        println!("No issues found matching JQL: {jql}");
+        return Ok(());
+    }
+
+    let planned: Vec<PlannedPage> = issues
+        .iter()
+        .filter_map(|issue| {
+            change.plan(
+                &issue.key,
+                issue.fields.summary.as_deref().unwrap_or_default(),
+                &issue_labels(issue),
+            )
+        })
+        .collect();
+
+    if dry_run {
+        println!("DRY RUN: Would {verb} labels on issues matching JQL: {jql}");
+        print_plan(&planned, "issues");
+        return Ok(());
+    }
+
+    for issue in &planned {
+        let (add, remove) = label_edits(issue);
+        client.update_issue_labels(&issue.tree.root_page, &add, &remove)?;
+        if verbose {
+            eprintln!(
+                "Applied {} actions to {}",
+                issue.tree.tag_actions.len(),
+                issue.tree.root_page
+            );
+        }
+    }
+
+    println!(
+        "Successfully changed labels on {} of {} issues:",
+        planned.len(),
+        issues.len()
+    );
+    for issue in &planned {
+        println!("  - {} {}", issue.tree.root_page, issue.title);
+    }
+    Ok(())
+}
+
+/// Find every issue matching a JQL query, with its summary and labels.
+fn search(client: &JiraClient, jql: &str) -> Result<Vec<JiraIssue>, Box<dyn Error>> {
+    let options = JiraSearchOptions {
+        fields: vec!["summary".to_string(), "labels".to_string()],
+        ..JiraSearchOptions::default()
+    };
+    Ok(client.search_issues(jql, &options)?)
+}
+
+/// Labels on an issue.
+fn issue_labels(issue: &JiraIssue) -> Vec<String> {
+    issue.fields.labels.clone().unwrap_or_default()
+}
+
+/// Print one line per issue, highlighting issues carrying any of the given labels.
+fn display_issues(issues: &[JiraIssue], highlight_tags: Option<&[String]>) {
+    if issues.is_empty() {
+        println!("No issues found.");
+        return;
+    }
+
+    for issue in issues {
+        let labels = issue_labels(issue);
+        let summary = issue.fields.summary.as_deref().unwrap_or_default();
+        let title = format!("{} {summary}", issue.key);
+        let highlighted =
+            highlight_tags.is_some_and(|tags| labels.iter().any(|l| tags.contains(l)));
+
+        let display_name = if highlighted {
+            format!("\x1b[1;33m{title}\x1b[0m") // Yellow highlight
+        } else {
+            title
+        };
+
+        if !labels.is_empty() {
+            println!("{} [{}]", display_name, labels.join(", "));
+        } else {
+            println!("{display_name}");
+        }
+    }
+}
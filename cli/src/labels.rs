@@ -0,0 +1,194 @@
+//! Label change planning shared by `ctag` and `jtag`.
+//!
+//! Both commands turn their arguments into a [`LabelChange`], diff it against
+//! the labels each page or issue carries, and print the resulting plan in the
+//! same format.
+
+use nix_rust_template::{ConfluencePageTree, PageLabelAction};
+use serde::Serialize;
+use std::error::Error;
+
+/// A planned page or issue with the labels it had before planning.
+#[derive(Debug)]
+pub struct PlannedPage {
+    /// Page title or issue summary
+    pub title: String,
+    /// Labels on the page before any action
+    pub before: Vec<String>,
+    /// Page ID or issue key, resulting labels and the actions to get there
+    pub tree: ConfluencePageTree,
+}
+
+/// A planned page, for JSON output.
+#[derive(Debug, Serialize)]
+pub struct PlanRow<'a> {
+    title: &'a str,
+    #[serde(flatten)]
+    tree: &'a ConfluencePageTree,
+}
+
+/// A label change requested on the command line.
+#[derive(Debug, Clone)]
+pub enum LabelChange {
+    /// Add labels that are missing
+    Add(Vec<String>),
+    /// Rename labels, as (old, new) pairs
+    Update(Vec<(String, String)>),
+    /// Remove labels that are present
+    Remove(Vec<String>),
+}
+
+impl LabelChange {
+    /// Plan the change against the current labels of a page or issue.
+    ///
+    /// Returns `None` when the labels already reflect the change.
+    pub fn plan(&self, id: &str, title: &str, current: &[String]) -> Option<PlannedPage> {
+        let mut tree = ConfluencePageTree::new(id);
+        tree.current_page_labels = current.to_vec();
+        match self {
+            LabelChange::Add(labels) => {
+                for label in labels {
+                    if !tree.current_page_labels.contains(label) {
+                        tree.add_label(label.clone());
+                    }
+                }
+            }
+            LabelChange::Update(updates) => {
+                for (from, to) in updates {
+                    if !tree.current_page_labels.contains(from) {
+                        continue;
+                    }
+                    if tree.current_page_labels.contains(to) {
+                        tree.delete_label(from);
+                    } else {
+                        tree.update_label(from, to);
+                    }
+                }
+            }
+            LabelChange::Remove(labels) => {
+                for label in labels {
+                    if tree.current_page_labels.contains(label) {
+                        tree.delete_label(label);
+                    }
+                }
+            }
+        }
+
+        if tree.tag_actions.is_empty() {
+            return None;
+        }
+        Some(PlannedPage {
+            title: title.to_string(),
+            before: current.to_vec(),
+            tree,
+        })
+    }
+}
+
+/// Parse a comma-separated list of label updates in the format "old:new".
+pub fn parse_updates(tags: &str) -> Result<Vec<(String, String)>, Box<dyn Error>> {
+    tags.split(',')
+        .map(|s: &str| s.trim())
+        .map(|update: &str| -> Result<(String, String), Box<dyn Error>> {
+            let parts: Vec<&str> = update.split(':').collect();
+            if parts.len() != 2 {
+                return Err(format!("Invalid update format '{update}'. Expected 'old:new'").into());
+            }
+            Ok((parts[0].trim().to_string(), parts[1].trim().to_string()))
+        })
+        .collect()
+}
+
+/// Split a comma-separated label list, dropping empty entries.
+pub fn parse_labels(tags: &str) -> Vec<String> {
+    tags.split(',')
+        .map(|s: &str| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Print planned label actions per page or issue, followed by a summary.
+///
+/// `noun` names what was planned in the summary line (e.g. "pages").
+pub fn print_plan(planned: &[PlannedPage], noun: &str) {
+    if planned.is_empty() {
+        println!("No changes. Labels are already up to date.");
+        return;
+    }
+
+    let (mut adds, mut updates, mut deletes) = (0, 0, 0);
+    for page in planned {
+        println!("{} ({})", page.title, page.tree.root_page);
+        for action in &page.tree.tag_actions {
+            match action {
+                PageLabelAction::Add { tag } => {
+                    adds += 1;
+                    println!("  + add {tag}");
+                }
+                PageLabelAction::Update { from, to } => {
+                    updates += 1;
+                    println!("  ~ update {from} -> {to}");
+                }
+                PageLabelAction::Delete { tag } => {
+                    deletes += 1;
+                    println!("  - delete {tag}");
+                }
+                PageLabelAction::List => {}
+            }
+        }
+    }
+    println!(
+        "Plan: {adds} to add, {updates} to update, {deletes} to delete across {} {noun}",
+        planned.len()
+    );
+}
+
+/// Planned pages as rows for JSON output.
+pub fn plan_rows(planned: &[PlannedPage]) -> Vec<PlanRow<'_>> {
+    planned
+        .iter()
+        .map(|p| PlanRow {
+            title: &p.title,
+            tree: &p.tree,
+        })
+        .collect()
+}
+
+/// Labels a page carries after adding and removing the given labels.
+pub fn labels_after(before: &[String], add: &[&str], remove: &[&str]) -> Vec<String> {
+    let mut after: Vec<String> = before
+        .iter()
+        .filter(|l| !remove.contains(&l.as_str()))
+        .cloned()
+        .collect();
+    for label in add {
+        if !remove.contains(label) && !after.iter().any(|l| l == label) {
+            after.push(label.to_string());
+        }
+    }
+    after
+}
+
+/// Labels to add and labels to remove to take a planned page from its
+/// labels before planning to its planned labels.
+///
+/// The edits are computed from the net difference rather than from the
+/// individual actions, so chained updates such as `foo:bar,baz:foo` do not
+/// remove a label that an earlier action added.
+pub fn label_edits(page: &PlannedPage) -> (Vec<&str>, Vec<&str>) {
+    let after = &page.tree.current_page_labels;
+    let mut add: Vec<&str> = Vec::new();
+    for label in after {
+        if !page.before.contains(label) && !add.contains(&label.as_str()) {
+            add.push(label);
+        }
+    }
+    let remove = page
+        .before
+        .iter()
+        .filter(|l| !after.contains(l))
+        .map(String::as_str)
+        .collect();
+    (add, remove)
+}
@@ -1,3 +1,49 @@
 //! Command-line interface library for the acli
 
+use nix_rust_template::{ConfluenceClient, ConfluenceConfig, JiraClient};
+use std::error::Error;
+
+pub mod attachment;
+pub mod comment;
 pub mod ctag;
+pub mod jira;
+pub mod journal;
+pub mod jtag;
+pub mod labels;
+pub mod manifest;
+pub mod output;
+pub mod page;
+pub mod propagate;
+pub mod restrict;
+pub mod rules;
+pub mod space;
+pub mod sync;
+pub mod taxonomy;
+
+/// Read the Atlassian site configuration from environment variables.
+pub fn load_atlassian_config() -> Result<ConfluenceConfig, Box<dyn Error>> {
+    dotenv::dotenv().ok(); // Load .env file, ignore if not found
+
+    let base_url =
+        std::env::var("ATLASSIAN_URL").map_err(|_| "ATLASSIAN_URL environment variable not set")?;
+    let username = std::env::var("ATLASSIAN_USERNAME")
+        .map_err(|_| "ATLASSIAN_USERNAME environment variable not set")?;
+    let api_token = std::env::var("ATLASSIAN_API_TOKEN")
+        .map_err(|_| "ATLASSIAN_API_TOKEN environment variable not set")?;
+
+    Ok(ConfluenceConfig {
+        base_url,
+        username,
+        api_token,
+    })
+}
+
+/// Create a Confluence client using environment variables.
+pub fn create_confluence_client() -> Result<ConfluenceClient, Box<dyn Error>> {
+    ConfluenceClient::new(load_atlassian_config()?).map_err(|e| e.into())
+}
+
+/// Create a Jira client using the same environment variables as Confluence.
+pub fn create_jira_client() -> Result<JiraClient, Box<dyn Error>> {
+    JiraClient::new(load_atlassian_config()?).map_err(|e| e.into())
+}
@@ -1,4 +1,4 @@
-use acli::ctag;
+use acli::{attachment, comment, ctag, jira, jtag, page, restrict, space, sync};
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
@@ -11,16 +11,16 @@ use std::path::PathBuf;
 /// Command-line interface for the acli
 struct Args {
     /// Log actions instead of executing actions
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     dry_run: bool,
     /// Pretty-print the JSON output
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     pretty: bool,
     /// Verbose output
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     verbose: bool,
     /// interactive mode
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     interactive: bool,
 
     /// Input JSON file path. Use '-' to read from stdin.
@@ -40,6 +40,22 @@ struct Args {
 enum Commands {
     /// Operate on Confluence page labels
     Ctag(ctag::CtagCmd),
+    /// List, upload and download page attachments
+    Attachment(attachment::AttachmentCmd),
+    /// List, add and resolve page comments
+    Comment(comment::CommentCmd),
+    /// Create, view, edit, transition, comment on and log time on Jira issues, plan sprints and graph issue links
+    Jira(jira::JiraCmd),
+    /// Operate on Jira issue labels
+    Jtag(jtag::JtagCmd),
+    /// Inspect page history and restructure page trees
+    Page(page::PageCmd),
+    /// View, change and audit page restrictions
+    Restrict(restrict::RestrictCmd),
+    /// Create, archive and delete spaces and export space permissions
+    Space(space::SpaceCmd),
+    /// Sync a local Markdown folder into a Confluence page tree
+    Sync(sync::SyncCmd),
 }
 
 /// todo: define action structs in their own files which will do interfacing
@@ -58,6 +74,30 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         Some(Commands::Ctag(ref cmd)) => {
             ctag::run(cmd, args.dry_run, args.pretty, args.verbose)?;
         }
+        Some(Commands::Attachment(ref cmd)) => {
+            attachment::run(cmd, args.dry_run, args.pretty, args.verbose)?;
+        }
+        Some(Commands::Comment(ref cmd)) => {
+            comment::run(cmd, args.dry_run, args.pretty, args.verbose)?;
+        }
+        Some(Commands::Jira(ref cmd)) => {
+            jira::run(cmd, args.dry_run, args.pretty, args.verbose)?;
+        }
+        Some(Commands::Jtag(ref cmd)) => {
+            jtag::run(cmd, args.dry_run, args.pretty, args.verbose)?;
+        }
+        Some(Commands::Page(ref cmd)) => {
+            page::run(cmd, args.dry_run, args.pretty, args.verbose)?;
+        }
+        Some(Commands::Restrict(ref cmd)) => {
+            restrict::run(cmd, args.dry_run, args.pretty, args.verbose)?;
+        }
+        Some(Commands::Space(ref cmd)) => {
+            space::run(cmd, args.dry_run, args.pretty, args.verbose)?;
+        }
+        Some(Commands::Sync(ref cmd)) => {
+            sync::run(cmd, args.dry_run, args.pretty, args.verbose)?;
+        }
         _ => {
             // todo: throw error command not provided and list --help
         }
@@ -0,0 +1,132 @@
+//! Declarative label manifests for `ctag plan` and `ctag apply`.
+//!
+//! A manifest maps CQL selectors or page IDs to the labels those pages should
+//! carry. It may be written in YAML or JSON:
+//!
+//! ```yaml
+//! mode: additive            # default mode for entries: additive or exact
+//! aliases:                  # labels rewritten to their canonical name
+//!   howto: how-to
+//! entries:
+//!   - cql: "ancestor = 1234"
+//!     labels: [runbook, ops]
+//!     mode: exact
+//!   - page: "5678"
+//!     labels: [policy]
+//! ```
+
+use crate::labels::PlannedPage;
+use nix_rust_template::{ConfluenceClient, ConfluencePage, ConfluencePageTree, LabelMode};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// A label manifest.
+#[derive(Debug, Deserialize)]
+pub struct Manifest {
+    /// Default mode for entries that do not set one
+    #[serde(default)]
+    pub mode: LabelMode,
+    /// Alias -> canonical label name
+    #[serde(default)]
+    pub aliases: HashMap<String, String>,
+    /// Desired label sets
+    pub entries: Vec<ManifestEntry>,
+}
+
+/// Desired labels for the pages selected by a CQL expression or page ID.
+#[derive(Debug, Deserialize)]
+pub struct ManifestEntry {
+    /// CQL expression selecting pages
+    pub cql: Option<String>,
+    /// Single page ID
+    pub page: Option<String>,
+    /// Labels the pages should carry
+    pub labels: Vec<String>,
+    /// Mode overriding the manifest default
+    pub mode: Option<LabelMode>,
+}
+
+impl Manifest {
+    /// Read a YAML or JSON manifest file.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let text = fs::read_to_string(path)?;
+        let manifest: Manifest = serde_yaml::from_str(&text)
+            .map_err(|e| format!("Invalid manifest {}: {e}", path.display()))?;
+        for (i, entry) in manifest.entries.iter().enumerate() {
+            if entry.cql.is_some() == entry.page.is_some() {
+                return Err(format!(
+                    "Manifest entry {} must set exactly one of `cql` or `page`",
+                    i + 1
+                )
+                .into());
+            }
+        }
+        Ok(manifest)
+    }
+
+    /// Resolve all entries against Confluence and plan the label actions.
+    ///
+    /// Pages selected by several entries get the union of their labels, and
+    /// are reconciled exactly if any of those entries is exact. Only pages
+    /// that need changes are returned.
+    pub fn plan(
+        &self,
+        client: &ConfluenceClient,
+        verbose: bool,
+    ) -> Result<Vec<PlannedPage>, Box<dyn Error>> {
+        let mut order: Vec<String> = Vec::new();
+        let mut targets: HashMap<String, (ConfluencePage, Vec<String>, LabelMode)> = HashMap::new();
+
+        for entry in &self.entries {
+            let pages = match (&entry.cql, &entry.page) {
+                (Some(cql), _) => client.query_all_pages_by_cql(cql)?,
+                (None, Some(page_id)) => vec![client.get_page(page_id, "metadata.labels")?],
+                (None, None) => Vec::new(),
+            };
+            if verbose {
+                eprintln!(
+                    "{} matched {} pages",
+                    entry.cql.as_deref().or(entry.page.as_deref()).unwrap_or(""),
+                    pages.len()
+                );
+            }
+
+            let mode = entry.mode.unwrap_or(self.mode);
+            for page in pages {
+                let (_, labels, page_mode) = targets.entry(page.id.clone()).or_insert_with(|| {
+                    order.push(page.id.clone());
+                    (page, Vec::new(), LabelMode::Additive)
+                });
+                for label in &entry.labels {
+                    if !labels.contains(label) {
+                        labels.push(label.clone());
+                    }
+                }
+                if mode == LabelMode::Exact {
+                    *page_mode = LabelMode::Exact;
+                }
+            }
+        }
+
+        let mut planned = Vec::new();
+        for page_id in order {
+            let Some((page, labels, mode)) = targets.remove(&page_id) else {
+                continue;
+            };
+            let mut tree = ConfluencePageTree::from_page(&page);
+            let before = tree.current_page_labels.clone();
+            tree.plan_labels(&labels, mode, &self.aliases);
+            if !tree.tag_actions.is_empty() {
+                planned.push(PlannedPage {
+                    title: page.title,
+                    before,
+                    tree,
+                });
+            }
+        }
+        Ok(planned)
+    }
+}
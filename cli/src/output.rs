@@ -0,0 +1,41 @@
+//! Output formatting shared by commands that print structured results.
+
+use clap::ValueEnum;
+use serde::Serialize;
+use std::error::Error;
+
+/// Output format for listing commands.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable table
+    #[default]
+    Table,
+    /// JSON document
+    Json,
+}
+
+/// Print a value as JSON, honouring the global `--pretty` flag.
+pub fn print_json<T: Serialize + ?Sized>(value: &T, pretty: bool) -> Result<(), Box<dyn Error>> {
+    let json = if pretty {
+        serde_json::to_string_pretty(value)?
+    } else {
+        serde_json::to_string(value)?
+    };
+    println!("{json}");
+    Ok(())
+}
+
+/// Format a byte count for humans (e.g. "1.5 MB").
+pub fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
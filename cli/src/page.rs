@@ -0,0 +1,355 @@
+use crate::create_confluence_client;
+use crate::output::{print_json, OutputFormat};
+use clap::{Args, Subcommand};
+use nix_rust_template::{storage_to_markdown, ConfluenceClient, ConfluencePage, CopyPageOptions};
+use similar::TextDiff;
+use std::collections::HashMap;
+use std::error::Error;
+
+/// page command: inspect and restructure individual Confluence pages.
+///
+/// Intended usage examples:
+///   acli page history 1234
+///   acli page diff 1234 --from 3 --to 7
+///   acli page move 1234 --parent 5678
+///   acli page copy 1234 --space ARCHIVE --recursive
+#[derive(Args, Debug)]
+pub struct PageCmd {
+    #[command(subcommand)]
+    pub operation: PageOp,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PageOp {
+    /// List the versions of a page with author, date and message
+    History {
+        /// Page ID
+        id: String,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Show a unified diff of a page's content between two versions, and its
+    /// current labels
+    Diff {
+        /// Page ID
+        id: String,
+        /// Version to diff from (defaults to the version before `--to`)
+        #[arg(long)]
+        from: Option<i32>,
+        /// Version to diff to (defaults to the current version)
+        #[arg(long)]
+        to: Option<i32>,
+    },
+    /// Move a page and all of its descendants under a new parent or into another space
+    Move {
+        /// Page ID
+        id: String,
+        #[command(flatten)]
+        target: TargetArgs,
+    },
+    /// Copy a page, with its labels and attachments, under a new parent or into another space
+    Copy {
+        /// Page ID
+        id: String,
+        #[command(flatten)]
+        target: TargetArgs,
+        /// Also copy all descendants of the page
+        #[arg(long)]
+        recursive: bool,
+        /// Prefix added to every copied title (needed when copying within a space)
+        #[arg(long)]
+        title_prefix: Option<String>,
+    },
+}
+
+/// Where a moved or copied page should end up.
+#[derive(Args, Debug)]
+#[group(required = true, multiple = false)]
+pub struct TargetArgs {
+    /// ID of the new parent page
+    #[arg(long)]
+    parent: Option<String>,
+    /// Key of the space to place the page in, under its home page
+    #[arg(long)]
+    space: Option<String>,
+}
+
+/// Execute the page command.
+pub fn run(
+    cmd: &PageCmd,
+    dry_run: bool,
+    pretty: bool,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+    match &cmd.operation {
+        PageOp::History { id, format } => {
+            if verbose {
+                eprintln!("Listing versions of page {id}");
+            }
+            let client = create_confluence_client()?;
+            let versions = client.get_page_versions(id)?;
+
+            match format {
+                OutputFormat::Json => print_json(&versions, pretty)?,
+                OutputFormat::Table => {
+                    for version in &versions {
+                        let author = version.by.as_ref().map_or("unknown", |by| by.name());
+                        let minor = if version.minor_edit == Some(true) {
+                            " (minor)"
+                        } else {
+                            ""
+                        };
+                        println!(
+                            "v{:<5} {}  {}{minor}  {}",
+                            version.number,
+                            version.when.as_deref().unwrap_or_default(),
+                            author,
+                            version.message.as_deref().unwrap_or_default()
+                        );
+                    }
+                }
+            }
+        }
+        PageOp::Diff { id, from, to } => {
+            let client = create_confluence_client()?;
+
+            let to = match to {
+                Some(to) => *to,
+                None => {
+                    let current = client.get_page(id, "version")?;
+                    current.version.map_or(1, |v| v.number)
+                }
+            };
+            let from = from.unwrap_or(to - 1);
+            if from < 1 || from >= to {
+                return Err(format!(
+                    "Invalid version range {from}..{to}: --from must be at least 1 and less than --to"
+                )
+                .into());
+            }
+            if verbose {
+                eprintln!("Diffing page {id} from version {from} to version {to}");
+            }
+
+            let old = client.get_page_at_version(id, from)?;
+            let new = client.get_page_at_version(id, to)?;
+
+            let old_content = page_markdown(&old);
+            let new_content = page_markdown(&new);
+            let content_diff = TextDiff::from_lines(&old_content, &new_content);
+            print!(
+                "{}",
+                content_diff
+                    .unified_diff()
+                    .context_radius(3)
+                    .header(&format!("{id} v{from}"), &format!("{id} v{to}"))
+            );
+
+            // Labels are not versioned, so every version reports the labels
+            // the page carries now.
+            let labels = sorted_labels(&new);
+            println!();
+            if labels.is_empty() {
+                println!("Current labels: none");
+            } else {
+                println!("Current labels: {}", labels.join(", "));
+            }
+            println!("(Confluence keeps no label history, so labels cannot be compared between versions.)");
+        }
+        PageOp::Move { id, target } => {
+            let client = create_confluence_client()?;
+            let parent = target.resolve(&client)?;
+            let page = client.get_page(id, "space")?;
+            let descendants = client.get_descendant_pages(id)?;
+            if verbose {
+                eprintln!(
+                    "Moving '{}' and {} descendants under '{}'",
+                    page.title,
+                    descendants.len(),
+                    parent.title
+                );
+            }
+
+            if dry_run {
+                println!(
+                    "DRY RUN: Would move '{}' ({} pages) under '{}' in {}:",
+                    page.title,
+                    descendants.len() + 1,
+                    parent.title,
+                    space_key(&parent)
+                );
+                print_tree(&parent, &page, &descendants, "");
+                return Ok(());
+            }
+
+            client.move_page(id, &parent.id)?;
+            println!(
+                "Moved '{}' ({} pages) under '{}' in {}",
+                page.title,
+                descendants.len() + 1,
+                parent.title,
+                space_key(&parent)
+            );
+        }
+        PageOp::Copy {
+            id,
+            target,
+            recursive,
+            title_prefix,
+        } => {
+            let client = create_confluence_client()?;
+            let parent = target.resolve(&client)?;
+            let page = client.get_page(id, "space")?;
+            let descendants = if *recursive {
+                client.get_descendant_pages(id)?
+            } else {
+                Vec::new()
+            };
+            let options = CopyPageOptions {
+                title_prefix: title_prefix.clone(),
+                ..CopyPageOptions::default()
+            };
+            if verbose {
+                eprintln!(
+                    "Copying '{}' and {} descendants under '{}'",
+                    page.title,
+                    descendants.len(),
+                    parent.title
+                );
+            }
+
+            if dry_run {
+                println!(
+                    "DRY RUN: Would copy '{}' ({} pages) under '{}' in {}:",
+                    page.title,
+                    descendants.len() + 1,
+                    parent.title,
+                    space_key(&parent)
+                );
+                print_tree(
+                    &parent,
+                    &page,
+                    &descendants,
+                    title_prefix.as_deref().unwrap_or_default(),
+                );
+                return Ok(());
+            }
+
+            if *recursive {
+                let task_id = client.copy_page_hierarchy(id, &parent.id, &options)?;
+                if verbose {
+                    eprintln!("Waiting for copy task {task_id}");
+                }
+                client.wait_for_long_task(&task_id)?;
+                println!(
+                    "Copied '{}' ({} pages) under '{}' in {}",
+                    page.title,
+                    descendants.len() + 1,
+                    parent.title,
+                    space_key(&parent)
+                );
+            } else {
+                let copy = client.copy_page(id, &parent.id, &options)?;
+                println!(
+                    "Copied '{}' to '{}' ({}) under '{}' in {}",
+                    page.title,
+                    copy.title,
+                    copy.id,
+                    parent.title,
+                    space_key(&parent)
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+impl TargetArgs {
+    /// Look up the page that will become the new parent.
+    fn resolve(&self, client: &ConfluenceClient) -> Result<ConfluencePage, Box<dyn Error>> {
+        let parent_id = match (&self.parent, &self.space) {
+            (Some(parent), _) => parent.clone(),
+            (None, Some(space)) => client.get_space_homepage_id(space)?,
+            (None, None) => return Err("Either --parent or --space is required".into()),
+        };
+        Ok(client.get_page(&parent_id, "space")?)
+    }
+}
+
+/// Key of the space a page lives in.
+fn space_key(page: &ConfluencePage) -> &str {
+    page.space
+        .as_ref()
+        .map_or("unknown space", |s| s.key.as_str())
+}
+
+/// Print the tree a page and its descendants would form under a new parent.
+fn print_tree(
+    parent: &ConfluencePage,
+    root: &ConfluencePage,
+    descendants: &[ConfluencePage],
+    title_prefix: &str,
+) {
+    let mut children: HashMap<&str, Vec<&ConfluencePage>> = HashMap::new();
+    for page in descendants {
+        if let Some(direct_parent) = page.ancestors.as_ref().and_then(|a| a.last()) {
+            children.entry(&direct_parent.id).or_default().push(page);
+        }
+    }
+
+    println!("{}", parent.title);
+    print_subtree(root, &children, title_prefix, "", true);
+}
+
+/// Recursively print one page of a tree and its children.
+fn print_subtree(
+    page: &ConfluencePage,
+    children: &HashMap<&str, Vec<&ConfluencePage>>,
+    title_prefix: &str,
+    indent: &str,
+    is_last: bool,
+) {
+    let (branch, continuation) = if is_last {
+        ("└── ", "    ")
+    } else {
+        ("├── ", "│   ")
+    };
+    println!("{indent}{branch}{title_prefix}{}", page.title);
+
+    let kids = children
+        .get(page.id.as_str())
+        .map_or(&[][..], Vec::as_slice);
+    for (i, child) in kids.iter().enumerate() {
+        print_subtree(
+            child,
+            children,
+            title_prefix,
+            &format!("{indent}{continuation}"),
+            i + 1 == kids.len(),
+        );
+    }
+}
+
+/// Render a page version as Markdown, with the title as a heading.
+fn page_markdown(page: &ConfluencePage) -> String {
+    let body = page
+        .body
+        .as_ref()
+        .and_then(|b| b.storage.as_ref())
+        .map(|s| storage_to_markdown(&s.value))
+        .unwrap_or_default();
+    format!("# {}\n\n{body}\n", page.title)
+}
+
+/// Labels of a page, in sorted order.
+fn sorted_labels(page: &ConfluencePage) -> Vec<&str> {
+    let mut labels: Vec<&str> = page
+        .metadata
+        .as_ref()
+        .and_then(|m| m.labels.as_ref())
+        .map(|l| l.results.iter().map(|l| l.name.as_str()).collect())
+        .unwrap_or_default();
+    labels.sort_unstable();
+    labels
+}
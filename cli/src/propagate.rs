@@ -0,0 +1,186 @@
+//! Label inheritance down a page tree for `ctag propagate`.
+//!
+//! Labels added by propagation are recorded in a content property on each
+//! page, keyed by the root page. `--sync` uses this record to remove the
+//! labels again from pages that have since left the subtree, without touching
+//! labels that were added by hand.
+
+use crate::labels::PlannedPage;
+use nix_rust_template::{ConfluenceClient, ConfluencePage, ConfluencePageTree, PageLabelAction};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::error::Error;
+
+/// Content property recording which labels propagation added to a page.
+const PROPAGATE_PROPERTY_KEY: &str = "acli-propagate";
+
+/// Options controlling how far labels propagate.
+#[derive(Debug)]
+pub struct Propagation<'a> {
+    /// ID of the root page
+    pub root_id: &'a str,
+    /// Labels to propagate
+    pub labels: &'a [&'a str],
+    /// Maximum depth below the root (0 labels only the root)
+    pub max_depth: Option<usize>,
+    /// Pages carrying any of these labels are skipped with their subtrees
+    pub exclude: &'a [&'a str],
+    /// Also remove propagated labels from pages no longer in the subtree
+    pub sync: bool,
+}
+
+impl Propagation<'_> {
+    /// Walk the subtree and plan the label actions.
+    pub fn plan(
+        &self,
+        client: &ConfluenceClient,
+        verbose: bool,
+    ) -> Result<Vec<PlannedPage>, Box<dyn Error>> {
+        let root = client.get_page(self.root_id, "metadata.labels")?;
+        let mut subtree = Vec::new();
+        self.walk(client, root, 0, &mut subtree)?;
+        if verbose {
+            eprintln!("Subtree of {} has {} pages", self.root_id, subtree.len());
+        }
+
+        let mut planned = Vec::new();
+        for page in &subtree {
+            let mut tree = ConfluencePageTree::from_page(page);
+            let before = tree.current_page_labels.clone();
+            for label in self.labels {
+                if !tree.current_page_labels.iter().any(|l| l == label) {
+                    tree.add_label(*label);
+                }
+            }
+            if !tree.tag_actions.is_empty() {
+                planned.push(PlannedPage {
+                    title: page.title.clone(),
+                    before,
+                    tree,
+                });
+            }
+        }
+
+        if self.sync {
+            let inside: HashSet<&str> = subtree.iter().map(|p| p.id.as_str()).collect();
+            let quoted: Vec<String> = self.labels.iter().map(|l| format!("\"{l}\"")).collect();
+            let cql = format!("type = page and label in ({})", quoted.join(","));
+            for page in client.query_all_pages_by_cql(&cql)? {
+                if inside.contains(page.id.as_str()) {
+                    continue;
+                }
+                let propagated = self.propagated_labels(client, &page.id)?;
+                let mut tree = ConfluencePageTree::from_page(&page);
+                let before = tree.current_page_labels.clone();
+                for label in propagated {
+                    if tree.current_page_labels.contains(&label) {
+                        tree.delete_label(&label);
+                    }
+                }
+                if !tree.tag_actions.is_empty() {
+                    planned.push(PlannedPage {
+                        title: page.title,
+                        before,
+                        tree,
+                    });
+                }
+            }
+        }
+
+        Ok(planned)
+    }
+
+    /// Update the propagation record of applied pages.
+    pub fn record(
+        &self,
+        client: &ConfluenceClient,
+        planned: &[PlannedPage],
+    ) -> Result<(), Box<dyn Error>> {
+        for page in planned {
+            let page_id = &page.tree.root_page;
+            let mut roots = match client.get_content_property(page_id, PROPAGATE_PROPERTY_KEY)? {
+                Some(property) => property.value["roots"]
+                    .as_object()
+                    .cloned()
+                    .unwrap_or_default(),
+                None => Map::new(),
+            };
+
+            let mut labels: Vec<String> = roots
+                .get(self.root_id)
+                .and_then(|v| serde_json::from_value(v.clone()).ok())
+                .unwrap_or_default();
+            for action in &page.tree.tag_actions {
+                match action {
+                    PageLabelAction::Add { tag } => labels.push(tag.clone()),
+                    PageLabelAction::Delete { tag } => labels.retain(|l| l != tag),
+                    _ => {}
+                }
+            }
+
+            if labels.is_empty() {
+                roots.remove(self.root_id);
+            } else {
+                roots.insert(self.root_id.to_string(), Value::from(labels));
+            }
+            client.set_content_property(
+                page_id,
+                PROPAGATE_PROPERTY_KEY,
+                serde_json::json!({ "roots": roots }),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Collect a page and its descendants, honouring depth and exclusions.
+    fn walk(
+        &self,
+        client: &ConfluenceClient,
+        page: ConfluencePage,
+        depth: usize,
+        out: &mut Vec<ConfluencePage>,
+    ) -> Result<(), Box<dyn Error>> {
+        let excluded = page
+            .metadata
+            .as_ref()
+            .and_then(|m| m.labels.as_ref())
+            .is_some_and(|l| {
+                l.results
+                    .iter()
+                    .any(|l| self.exclude.contains(&l.name.as_str()))
+            });
+        if excluded {
+            return Ok(());
+        }
+
+        let id = page.id.clone();
+        out.push(page);
+        if self.max_depth.is_some_and(|max| depth >= max) {
+            return Ok(());
+        }
+
+        for child in client.query_all_pages_by_cql(&format!("parent = {id}"))? {
+            self.walk(client, child, depth + 1, out)?;
+        }
+        Ok(())
+    }
+
+    /// Labels that propagation from this root added to a page, limited to
+    /// the labels being propagated now.
+    fn propagated_labels(
+        &self,
+        client: &ConfluenceClient,
+        page_id: &str,
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        let Some(property) = client.get_content_property(page_id, PROPAGATE_PROPERTY_KEY)? else {
+            return Ok(Vec::new());
+        };
+        let labels: Vec<String> =
+            serde_json::from_value(property.value["roots"][self.root_id].clone())
+                .unwrap_or_default();
+        Ok(labels
+            .into_iter()
+            .filter(|l| self.labels.contains(&l.as_str()))
+            .collect())
+    }
+}
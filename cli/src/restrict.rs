@@ -0,0 +1,297 @@
+use crate::create_confluence_client;
+use crate::output::{print_json, OutputFormat};
+use clap::{Args, Subcommand, ValueEnum};
+use nix_rust_template::{
+    ConfluencePage, PageRestrictions, RestrictionOperation, RestrictionSet, RestrictionSubject,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::error::Error;
+
+/// restrict command: view, change and audit page read/update restrictions on
+/// pages matched by a CQL expression.
+///
+/// Intended usage examples:
+///   acli restrict list "space = HR"
+///   acli restrict add "ancestor = 1234" --operation read --group hr-team
+///   acli restrict remove "ancestor = 1234" --operation update --user 5b10ac8d82e05b22cc7d4ef5
+///   acli restrict report "space = HR"
+#[derive(Args, Debug)]
+pub struct RestrictCmd {
+    #[command(subcommand)]
+    pub operation: RestrictOp,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum RestrictOp {
+    /// List read and update restrictions of pages matching the CQL expression
+    List {
+        /// CQL expression selecting pages to operate on
+        cql: String,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Add users or groups to a restriction on pages matching the CQL expression
+    Add {
+        /// CQL expression selecting pages to operate on
+        cql: String,
+        #[command(flatten)]
+        change: RestrictionChange,
+    },
+    /// Remove users or groups from a restriction on pages matching the CQL expression
+    Remove {
+        /// CQL expression selecting pages to operate on
+        cql: String,
+        #[command(flatten)]
+        change: RestrictionChange,
+    },
+    /// Report pages whose restrictions differ from their parent page's
+    Report {
+        /// CQL expression selecting pages to operate on
+        cql: String,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+}
+
+/// Users and groups to add to or remove from a restriction.
+#[derive(Args, Debug)]
+pub struct RestrictionChange {
+    /// Restricted operation
+    #[arg(long, value_enum)]
+    operation: OperationArg,
+    /// Atlassian account ID of a user (repeatable)
+    #[arg(long, required_unless_present = "group")]
+    user: Vec<String>,
+    /// Group name (repeatable)
+    #[arg(long)]
+    group: Vec<String>,
+}
+
+/// Restricted operation as given on the command line.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OperationArg {
+    /// Viewing the page
+    Read,
+    /// Editing the page
+    Update,
+}
+
+/// Restrictions of one page, for JSON output.
+#[derive(Debug, Serialize)]
+struct RestrictionRow<'a> {
+    id: &'a str,
+    title: &'a str,
+    restrictions: &'a PageRestrictions,
+}
+
+/// A difference between a page's restrictions and its parent's.
+#[derive(Debug, Serialize)]
+struct RestrictionDrift {
+    id: String,
+    title: String,
+    parent_id: String,
+    parent_title: String,
+    operation: RestrictionOperation,
+    only_on_page: Vec<RestrictionSubject>,
+    only_on_parent: Vec<RestrictionSubject>,
+}
+
+/// Execute the restrict command.
+pub fn run(
+    cmd: &RestrictCmd,
+    dry_run: bool,
+    pretty: bool,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+    let client = create_confluence_client()?;
+
+    match &cmd.operation {
+        RestrictOp::List { cql, format } => {
+            if verbose {
+                eprintln!("Listing restrictions on pages matching: {cql}");
+            }
+            let pages = client.query_all_pages_by_cql(cql)?;
+            let mut listing: Vec<(ConfluencePage, PageRestrictions)> = Vec::new();
+            for page in pages {
+                let restrictions = client.get_page_restrictions(&page.id)?;
+                listing.push((page, restrictions));
+            }
+
+            match format {
+                OutputFormat::Json => {
+                    let rows: Vec<RestrictionRow> = listing
+                        .iter()
+                        .map(|(page, restrictions)| RestrictionRow {
+                            id: &page.id,
+                            title: &page.title,
+                            restrictions,
+                        })
+                        .collect();
+                    print_json(&rows, pretty)?;
+                }
+                OutputFormat::Table => {
+                    if listing.is_empty() {
+                        println!("No pages found matching CQL: {cql}");
+                    }
+                    for (page, restrictions) in &listing {
+                        println!("{} ({})", page.title, page.id);
+                        println!("  read:   {}", describe(&restrictions.read));
+                        println!("  update: {}", describe(&restrictions.update));
+                    }
+                }
+            }
+        }
+        RestrictOp::Add { cql, change } | RestrictOp::Remove { cql, change } => {
+            let adding = matches!(cmd.operation, RestrictOp::Add { .. });
+            let action = if adding { "add" } else { "remove" };
+            let operation: RestrictionOperation = change.operation.into();
+            let subjects = change.subjects();
+            if verbose {
+                eprintln!(
+                    "Attempting to {action} {} restriction for {subjects:?} on pages matching: {cql}",
+                    operation.as_str()
+                );
+            }
+
+            let pages = client.query_all_pages_by_cql(cql)?;
+            if pages.is_empty() {
+                println!("No pages found matching CQL: {cql}");
+                return Ok(());
+            }
+
+            for page in &pages {
+                for subject in &subjects {
+                    if dry_run {
+                        println!(
+                            "DRY RUN: Would {action} {} restriction for {subject} on '{}'",
+                            operation.as_str(),
+                            page.title
+                        );
+                    } else if adding {
+                        client.add_page_restriction(&page.id, operation, subject)?;
+                        println!(
+                            "Added {} restriction for {subject} on '{}'",
+                            operation.as_str(),
+                            page.title
+                        );
+                    } else {
+                        client.remove_page_restriction(&page.id, operation, subject)?;
+                        println!(
+                            "Removed {} restriction for {subject} on '{}'",
+                            operation.as_str(),
+                            page.title
+                        );
+                    }
+                }
+            }
+        }
+        RestrictOp::Report { cql, format } => {
+            if verbose {
+                eprintln!("Comparing restrictions with parent pages for pages matching: {cql}");
+            }
+            let pages = client.query_all_pages_by_cql(cql)?;
+
+            let mut parents: HashMap<String, PageRestrictions> = HashMap::new();
+            let mut drifts = Vec::new();
+            for page in &pages {
+                let Some(parent) = page.ancestors.as_ref().and_then(|a| a.last()) else {
+                    continue;
+                };
+                if !parents.contains_key(&parent.id) {
+                    let restrictions = client.get_page_restrictions(&parent.id)?;
+                    parents.insert(parent.id.clone(), restrictions);
+                }
+                let parent_restrictions = &parents[&parent.id];
+                let restrictions = client.get_page_restrictions(&page.id)?;
+
+                for operation in [RestrictionOperation::Read, RestrictionOperation::Update] {
+                    let own = restrictions.get(operation).subjects();
+                    let inherited = parent_restrictions.get(operation).subjects();
+                    if own == inherited {
+                        continue;
+                    }
+                    drifts.push(RestrictionDrift {
+                        id: page.id.clone(),
+                        title: page.title.clone(),
+                        parent_id: parent.id.clone(),
+                        parent_title: parent.title.clone(),
+                        operation,
+                        only_on_page: own
+                            .iter()
+                            .filter(|s| !inherited.contains(s))
+                            .cloned()
+                            .collect(),
+                        only_on_parent: inherited
+                            .iter()
+                            .filter(|s| !own.contains(s))
+                            .cloned()
+                            .collect(),
+                    });
+                }
+            }
+
+            match format {
+                OutputFormat::Json => print_json(&drifts, pretty)?,
+                OutputFormat::Table => {
+                    if drifts.is_empty() {
+                        println!("All pages match their parent's restrictions.");
+                    }
+                    for drift in &drifts {
+                        println!(
+                            "{} ({}): {} restrictions differ from parent '{}' ({})",
+                            drift.title,
+                            drift.id,
+                            drift.operation.as_str(),
+                            drift.parent_title,
+                            drift.parent_id
+                        );
+                        for subject in &drift.only_on_page {
+                            println!("  + {subject}");
+                        }
+                        for subject in &drift.only_on_parent {
+                            println!("  - {subject}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Summarise a restriction set for table output.
+fn describe(set: &RestrictionSet) -> String {
+    if set.is_empty() {
+        return "unrestricted".to_string();
+    }
+    set.users
+        .iter()
+        .map(|u| format!("user:{}", u.name()))
+        .chain(set.groups.iter().map(|g| format!("group:{g}")))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl RestrictionChange {
+    /// Users and groups named on the command line.
+    fn subjects(&self) -> Vec<RestrictionSubject> {
+        self.user
+            .iter()
+            .cloned()
+            .map(RestrictionSubject::User)
+            .chain(self.group.iter().cloned().map(RestrictionSubject::Group))
+            .collect()
+    }
+}
+
+impl From<OperationArg> for RestrictionOperation {
+    fn from(arg: OperationArg) -> Self {
+        match arg {
+            OperationArg::Read => RestrictionOperation::Read,
+            OperationArg::Update => RestrictionOperation::Update,
+        }
+    }
+}
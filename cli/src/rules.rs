@@ -0,0 +1,229 @@
+//! Rule-based auto-labeling for `ctag rules run`.
+//!
+//! A rules file names a CQL scope and a list of rules. Each rule pairs a
+//! matcher with labels to add or remove; every condition set on a matcher
+//! must hold for the rule to apply:
+//!
+//! ```yaml
+//! scope: "space = OPS"
+//! rules:
+//!   - name: runbooks
+//!     match:
+//!       ancestor: Runbooks        # ancestor page title or ID
+//!     add: [runbook]
+//!   - name: stale drafts
+//!     match:
+//!       title: "(?i)^draft"       # regular expression
+//!       older_than: 180d          # last modified more than 180 days ago
+//!     add: [stale]
+//!     remove: [in-progress]
+//! ```
+//!
+//! Other conditions are `space` (space key), `author` (creator account ID or
+//! display name), `newer_than` and `body` (regular expression over the page
+//! text).
+
+use crate::labels::PlannedPage;
+use chrono::{DateTime, Utc};
+use nix_rust_template::{
+    storage_to_markdown, ConfluenceClient, ConfluencePage, ConfluencePageTree,
+};
+use regex::Regex;
+use serde::Deserialize;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// Expansions needed to evaluate every matcher except `body`.
+const RULE_EXPAND: &str = "metadata.labels,ancestors,space,version,history";
+
+/// A rules file.
+#[derive(Debug, Deserialize)]
+pub struct RuleSet {
+    /// CQL expression selecting the pages rules are evaluated against
+    pub scope: Option<String>,
+    /// Rules, applied in order
+    pub rules: Vec<Rule>,
+}
+
+/// A matcher with the labels to add and remove on matching pages.
+#[derive(Debug, Deserialize)]
+pub struct Rule {
+    /// Name shown in verbose output
+    pub name: String,
+    /// Conditions a page must meet
+    #[serde(rename = "match")]
+    pub matcher: Matcher,
+    /// Labels to add to matching pages
+    #[serde(default)]
+    pub add: Vec<String>,
+    /// Labels to remove from matching pages
+    #[serde(default)]
+    pub remove: Vec<String>,
+}
+
+/// Conditions on a page; unset conditions always hold.
+#[derive(Debug, Default, Deserialize)]
+pub struct Matcher {
+    /// Regular expression the title must match
+    #[serde(default, with = "serde_regex")]
+    pub title: Option<Regex>,
+    /// Title or ID of a page the page must be below
+    pub ancestor: Option<String>,
+    /// Key of the space the page must be in
+    pub space: Option<String>,
+    /// Account ID or display name of the page's creator
+    pub author: Option<String>,
+    /// Minimum time since the last modification (e.g. "90d")
+    #[serde(default, with = "humantime_serde")]
+    pub older_than: Option<Duration>,
+    /// Maximum time since the last modification (e.g. "7d")
+    #[serde(default, with = "humantime_serde")]
+    pub newer_than: Option<Duration>,
+    /// Regular expression the page text must match
+    #[serde(default, with = "serde_regex")]
+    pub body: Option<Regex>,
+}
+
+impl RuleSet {
+    /// Read a YAML or JSON rules file.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let text = fs::read_to_string(path)?;
+        let rules: RuleSet = serde_yaml::from_str(&text)
+            .map_err(|e| format!("Invalid rules file {}: {e}", path.display()))?;
+        Ok(rules)
+    }
+
+    /// Evaluate the rules against every page in scope and plan the label actions.
+    ///
+    /// `cql` overrides the scope from the rules file. Only pages that need
+    /// changes are returned.
+    pub fn plan(
+        &self,
+        client: &ConfluenceClient,
+        cql: Option<&str>,
+        verbose: bool,
+    ) -> Result<Vec<PlannedPage>, Box<dyn Error>> {
+        let scope = cql
+            .or(self.scope.as_deref())
+            .ok_or("No scope: set `scope` in the rules file or pass --cql")?;
+        let expand = if self.rules.iter().any(|r| r.matcher.body.is_some()) {
+            format!("{RULE_EXPAND},body.storage")
+        } else {
+            RULE_EXPAND.to_string()
+        };
+        let pages = client.query_all_pages_by_cql_with_expand(scope, &expand)?;
+        if verbose {
+            eprintln!(
+                "Evaluating {} rules against {} pages",
+                self.rules.len(),
+                pages.len()
+            );
+        }
+
+        let now = Utc::now();
+        let mut planned = Vec::new();
+        for page in pages {
+            let mut tree = ConfluencePageTree::from_page(&page);
+            let before = tree.current_page_labels.clone();
+            for rule in &self.rules {
+                if !rule.matcher.matches(&page, now) {
+                    continue;
+                }
+                if verbose {
+                    eprintln!("Rule '{}' matches '{}'", rule.name, page.title);
+                }
+                for label in &rule.add {
+                    if !tree.current_page_labels.contains(label) {
+                        tree.add_label(label.clone());
+                    }
+                }
+                for label in &rule.remove {
+                    if tree.current_page_labels.contains(label) {
+                        tree.delete_label(label);
+                    }
+                }
+            }
+            if !tree.tag_actions.is_empty() {
+                planned.push(PlannedPage {
+                    title: page.title,
+                    before,
+                    tree,
+                });
+            }
+        }
+        Ok(planned)
+    }
+}
+
+impl Matcher {
+    /// Check every set condition against a page.
+    pub fn matches(&self, page: &ConfluencePage, now: DateTime<Utc>) -> bool {
+        if let Some(title) = &self.title {
+            if !title.is_match(&page.title) {
+                return false;
+            }
+        }
+
+        if let Some(ancestor) = &self.ancestor {
+            let below = page
+                .ancestors
+                .iter()
+                .flatten()
+                .any(|a| &a.id == ancestor || &a.title == ancestor);
+            if !below {
+                return false;
+            }
+        }
+
+        if let Some(space) = &self.space {
+            if page.space.as_ref().is_none_or(|s| &s.key != space) {
+                return false;
+            }
+        }
+
+        if let Some(author) = &self.author {
+            let creator = page.history.as_ref().and_then(|h| h.created_by.as_ref());
+            let is_author = creator.is_some_and(|c| {
+                c.account_id.as_ref() == Some(author) || c.display_name.as_ref() == Some(author)
+            });
+            if !is_author {
+                return false;
+            }
+        }
+
+        if self.older_than.is_some() || self.newer_than.is_some() {
+            let Some(age) = last_modified_age(page, now) else {
+                return false;
+            };
+            if self.older_than.is_some_and(|min| age < min) {
+                return false;
+            }
+            if self.newer_than.is_some_and(|max| age > max) {
+                return false;
+            }
+        }
+
+        if let Some(body) = &self.body {
+            let text = page
+                .body
+                .as_ref()
+                .and_then(|b| b.storage.as_ref())
+                .map(|s| storage_to_markdown(&s.value))
+                .unwrap_or_default();
+            if !body.is_match(&text) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Time since the page was last modified.
+fn last_modified_age(page: &ConfluencePage, now: DateTime<Utc>) -> Option<Duration> {
+    let when = page.version.as_ref()?.when.as_deref()?;
+    let modified = DateTime::parse_from_rfc3339(when).ok()?;
+    (now - modified.with_timezone(&Utc)).to_std().ok()
+}
@@ -0,0 +1,421 @@
+use crate::create_confluence_client;
+use crate::output::{print_json, OutputFormat};
+use clap::{Args, Subcommand, ValueEnum};
+use nix_rust_template::{
+    ConfluenceSpace, PermissionSubject, SpaceKind, SpacePermission, SpaceUpdate,
+};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+/// space command: administer Confluence spaces.
+///
+/// Intended usage examples:
+///   acli space list --archived
+///   acli space create OPS --name "Operations" --description "Runbooks and on-call"
+///   acli space update OPS --name "Platform Operations"
+///   acli space archive OLDPROJ
+///   acli space restore OLDPROJ
+///   acli space delete OLDPROJ
+///   acli space permissions HR --format csv > hr-permissions.csv
+///   acli space permissions HR --fail-on-public
+///   acli space permissions diff HR FINANCE
+#[derive(Args, Debug)]
+pub struct SpaceCmd {
+    #[command(subcommand)]
+    pub operation: SpaceOp,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SpaceOp {
+    /// List spaces
+    List {
+        /// List archived spaces instead of current ones
+        #[arg(long)]
+        archived: bool,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Create a space
+    Create {
+        /// Space key
+        key: String,
+        /// Space name
+        #[arg(long)]
+        name: String,
+        /// Plain-text description
+        #[arg(long)]
+        description: Option<String>,
+        /// Kind of space
+        #[arg(long = "type", value_enum, default_value_t)]
+        space_type: SpaceTypeArg,
+    },
+    /// Update the name or description of a space
+    Update {
+        /// Space key
+        key: String,
+        /// New space name
+        #[arg(long, required_unless_present = "description")]
+        name: Option<String>,
+        /// New plain-text description
+        #[arg(long)]
+        description: Option<String>,
+    },
+    /// Archive a space
+    Archive {
+        /// Space key
+        key: String,
+    },
+    /// Restore an archived space
+    Restore {
+        /// Space key
+        key: String,
+    },
+    /// Delete a space, after typing its key to confirm
+    Delete {
+        /// Space key
+        key: String,
+        /// Space key, repeated to confirm without a prompt
+        #[arg(long)]
+        confirm: Option<String>,
+    },
+    /// Export the permission matrix of a space, or compare two spaces
+    Permissions(PermissionsArgs),
+}
+
+/// Arguments for `space permissions`.
+#[derive(Args, Debug)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+pub struct PermissionsArgs {
+    #[command(subcommand)]
+    operation: Option<PermissionsOp>,
+    /// Space key
+    #[arg(required = true)]
+    key: Option<String>,
+    /// Output format
+    #[arg(long, value_enum, default_value_t)]
+    format: MatrixFormat,
+    /// Fail if anonymous, unlicensed or all-users groups can view the space
+    #[arg(long)]
+    fail_on_public: bool,
+    /// Group that contains every user on the site (repeatable)
+    #[arg(long, default_value = "confluence-users")]
+    all_users_group: Vec<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PermissionsOp {
+    /// Compare the permissions of two spaces
+    Diff {
+        /// Space key to compare from
+        a: String,
+        /// Space key to compare to
+        b: String,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+}
+
+/// Kind of space as given on the command line.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SpaceTypeArg {
+    /// Space visible according to its permissions
+    #[default]
+    Global,
+    /// Space visible only to its creator
+    Private,
+}
+
+/// Output format for a permission matrix.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MatrixFormat {
+    /// Operations listed per subject
+    #[default]
+    Table,
+    /// JSON document
+    Json,
+    /// Subject × operation matrix as CSV
+    Csv,
+}
+
+/// Operations granted to one subject, for JSON output.
+#[derive(Debug, Serialize)]
+struct SubjectPermissions<'a> {
+    subject: &'a PermissionSubject,
+    operations: &'a BTreeSet<String>,
+}
+
+/// Permissions present in only one of two spaces.
+#[derive(Debug, Serialize)]
+struct PermissionDiff<'a> {
+    only_in_a: Vec<&'a SpacePermission>,
+    only_in_b: Vec<&'a SpacePermission>,
+}
+
+/// Execute the space command.
+pub fn run(
+    cmd: &SpaceCmd,
+    dry_run: bool,
+    pretty: bool,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+    let client = create_confluence_client()?;
+
+    match &cmd.operation {
+        SpaceOp::List { archived, format } => {
+            if verbose {
+                eprintln!("Listing spaces");
+            }
+            let spaces = if *archived {
+                client.get_archived_spaces()?
+            } else {
+                client.get_spaces()?
+            };
+
+            match format {
+                OutputFormat::Json => print_json(&spaces, pretty)?,
+                OutputFormat::Table => {
+                    if spaces.is_empty() {
+                        println!("No spaces found.");
+                    }
+                    for space in &spaces {
+                        println!(
+                            "{:<12} {:<8} {:<9} {}",
+                            space.key, space.space_type, space.status, space.name
+                        );
+                    }
+                }
+            }
+        }
+        SpaceOp::Create {
+            key,
+            name,
+            description,
+            space_type,
+        } => {
+            if dry_run {
+                println!("DRY RUN: Would create space {key} '{name}'");
+                return Ok(());
+            }
+            let space =
+                client.create_space(key, name, description.as_deref(), (*space_type).into())?;
+            print_space("Created", &space);
+        }
+        SpaceOp::Update {
+            key,
+            name,
+            description,
+        } => {
+            if dry_run {
+                println!("DRY RUN: Would update space {key}");
+                return Ok(());
+            }
+            let update = SpaceUpdate {
+                name: name.clone(),
+                description: description.clone(),
+                ..SpaceUpdate::default()
+            };
+            let space = client.update_space(key, &update)?;
+            print_space("Updated", &space);
+        }
+        SpaceOp::Archive { key } => {
+            if dry_run {
+                println!("DRY RUN: Would archive space {key}");
+                return Ok(());
+            }
+            let space = client.archive_space(key)?;
+            print_space("Archived", &space);
+        }
+        SpaceOp::Restore { key } => {
+            if dry_run {
+                println!("DRY RUN: Would restore space {key}");
+                return Ok(());
+            }
+            let space = client.restore_space(key)?;
+            print_space("Restored", &space);
+        }
+        SpaceOp::Delete { key, confirm } => {
+            let space = client.get_space(key)?;
+            if dry_run {
+                println!("DRY RUN: Would delete space {} '{}'", space.key, space.name);
+                return Ok(());
+            }
+
+            let typed = match confirm {
+                Some(confirm) => confirm.clone(),
+                None => {
+                    eprint!(
+                        "Deleting space {} '{}' moves all of its content to the trash.\nType the space key to confirm: ",
+                        space.key, space.name
+                    );
+                    io::stderr().flush()?;
+                    let mut line = String::new();
+                    io::stdin().lock().read_line(&mut line)?;
+                    line.trim().to_string()
+                }
+            };
+            if typed != space.key {
+                return Err(format!(
+                    "Confirmation '{typed}' does not match space key {}; nothing deleted",
+                    space.key
+                )
+                .into());
+            }
+
+            let task_id = client.delete_space(&space.key)?;
+            if verbose {
+                eprintln!("Waiting for delete task {task_id}");
+            }
+            client.wait_for_long_task(&task_id)?;
+            println!("Deleted space {} '{}'", space.key, space.name);
+        }
+        SpaceOp::Permissions(args) => match &args.operation {
+            Some(PermissionsOp::Diff { a, b, format }) => {
+                if verbose {
+                    eprintln!("Comparing permissions of spaces {a} and {b}");
+                }
+                let a_permissions = client.get_space_permissions(a)?;
+                let b_permissions = client.get_space_permissions(b)?;
+                let diff = PermissionDiff {
+                    only_in_a: a_permissions
+                        .iter()
+                        .filter(|p| !b_permissions.contains(p))
+                        .collect(),
+                    only_in_b: b_permissions
+                        .iter()
+                        .filter(|p| !a_permissions.contains(p))
+                        .collect(),
+                };
+
+                match format {
+                    OutputFormat::Json => print_json(&diff, pretty)?,
+                    OutputFormat::Table => {
+                        if diff.only_in_a.is_empty() && diff.only_in_b.is_empty() {
+                            println!("Spaces {a} and {b} have identical permissions.");
+                            return Ok(());
+                        }
+                        println!("--- {a}");
+                        println!("+++ {b}");
+                        for permission in &diff.only_in_a {
+                            println!("- {} {}", permission.subject, permission.operation_key());
+                        }
+                        for permission in &diff.only_in_b {
+                            println!("+ {} {}", permission.subject, permission.operation_key());
+                        }
+                    }
+                }
+            }
+            None => {
+                let key = args.key.as_deref().ok_or("A space key is required")?;
+                if verbose {
+                    eprintln!("Exporting permissions of space {key}");
+                }
+                let permissions = client.get_space_permissions(key)?;
+                let matrix = permission_matrix(&permissions);
+
+                match args.format {
+                    MatrixFormat::Json => {
+                        let rows: Vec<SubjectPermissions> = matrix
+                            .iter()
+                            .map(|(subject, operations)| SubjectPermissions {
+                                subject,
+                                operations,
+                            })
+                            .collect();
+                        print_json(&rows, pretty)?;
+                    }
+                    MatrixFormat::Csv => write_csv(&matrix)?,
+                    MatrixFormat::Table => {
+                        if matrix.is_empty() {
+                            println!("No permissions found for space {key}");
+                        }
+                        for (subject, operations) in &matrix {
+                            println!("{subject}");
+                            let operations: Vec<&str> =
+                                operations.iter().map(String::as_str).collect();
+                            println!("  {}", operations.join(", "));
+                        }
+                    }
+                }
+
+                if args.fail_on_public {
+                    let public: Vec<String> = permissions
+                        .iter()
+                        .filter(|p| p.operation == "read" && p.target == "space")
+                        .filter(|p| match &p.subject {
+                            PermissionSubject::Group { name } => {
+                                args.all_users_group.contains(name)
+                            }
+                            subject => subject.is_public(),
+                        })
+                        .map(|p| p.subject.to_string())
+                        .collect();
+                    if !public.is_empty() {
+                        return Err(
+                            format!("Space {key} can be viewed by: {}", public.join(", ")).into(),
+                        );
+                    }
+                }
+            }
+        },
+    }
+    Ok(())
+}
+
+/// Print the outcome of a space lifecycle operation.
+fn print_space(action: &str, space: &ConfluenceSpace) {
+    println!(
+        "{action} space {} '{}' ({})",
+        space.key, space.name, space.status
+    );
+}
+
+/// Group permissions into the operations granted to each subject.
+fn permission_matrix(
+    permissions: &[SpacePermission],
+) -> BTreeMap<PermissionSubject, BTreeSet<String>> {
+    let mut matrix: BTreeMap<PermissionSubject, BTreeSet<String>> = BTreeMap::new();
+    for permission in permissions {
+        matrix
+            .entry(permission.subject.clone())
+            .or_default()
+            .insert(permission.operation_key());
+    }
+    matrix
+}
+
+/// Write the permission matrix to stdout as CSV, one row per subject.
+fn write_csv(matrix: &BTreeMap<PermissionSubject, BTreeSet<String>>) -> Result<(), Box<dyn Error>> {
+    let operations: BTreeSet<&String> = matrix.values().flatten().collect();
+
+    let mut writer = csv::Writer::from_writer(io::stdout());
+    let mut header = vec!["subject"];
+    header.extend(operations.iter().map(|o| o.as_str()));
+    writer.write_record(&header)?;
+
+    for (subject, granted) in matrix {
+        let mut record = vec![subject.to_string()];
+        record.extend(operations.iter().map(|o| {
+            if granted.contains(*o) {
+                "x".to_string()
+            } else {
+                String::new()
+            }
+        }));
+        writer.write_record(&record)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+impl From<SpaceTypeArg> for SpaceKind {
+    fn from(arg: SpaceTypeArg) -> Self {
+        match arg {
+            SpaceTypeArg::Global => SpaceKind::Global,
+            SpaceTypeArg::Private => SpaceKind::Private,
+        }
+    }
+}
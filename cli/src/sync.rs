@@ -0,0 +1,518 @@
+use crate::create_confluence_client;
+use clap::{Args, Subcommand};
+use nix_rust_template::{markdown_to_storage, ConfluenceClient, ConfluencePage, StorageBody};
+use serde::Deserialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Content property used to remember which source file a page came from.
+const SYNC_PROPERTY_KEY: &str = "acli-sync";
+
+/// File names that describe the page of the directory containing them.
+const INDEX_FILES: [&str; 2] = ["index.md", "README.md"];
+
+/// sync command: publish a folder of Markdown files as a Confluence page tree.
+///
+/// Intended usage examples:
+///   acli sync push ./docs --space DOCS --parent 1234
+///   acli sync push ./docs --space DOCS --parent 1234 --prune --dry-run
+///
+/// Every Markdown file becomes one page and every sub-folder becomes a page
+/// whose children are the files inside it. A folder's own content comes from
+/// its `index.md` or `README.md`; without one an empty page is created. Front
+/// matter may set `title` and `labels`.
+///
+/// Pages are matched to files by the source path recorded on them, among the
+/// descendants of the parent page, so renaming a title updates the existing
+/// page. A page with the same title that sync did not create is reported as a
+/// conflict and left alone.
+#[derive(Args, Debug)]
+pub struct SyncCmd {
+    #[command(subcommand)]
+    pub operation: SyncOp,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SyncOp {
+    /// Create or update one page per Markdown file under a parent page
+    Push {
+        /// Directory containing the Markdown files
+        dir: PathBuf,
+        /// Key of the space to publish into
+        #[arg(long)]
+        space: String,
+        /// ID of the page the tree is published under
+        #[arg(long)]
+        parent: String,
+        /// Archive previously synced pages whose source file was deleted
+        #[arg(long)]
+        prune: bool,
+    },
+}
+
+/// Front matter recognised at the top of a Markdown file.
+#[derive(Debug, Default, Deserialize)]
+struct FrontMatter {
+    /// Page title; defaults to the first heading or the file name
+    title: Option<String>,
+    /// Labels applied to the page
+    #[serde(default)]
+    labels: Vec<String>,
+}
+
+/// A page built from a Markdown file or a directory.
+#[derive(Debug)]
+struct SourcePage {
+    /// Path relative to the sync root, used as the stable identity of the page
+    source: String,
+    /// Page title
+    title: String,
+    /// Page body in storage format
+    body: String,
+    /// Labels from front matter
+    labels: Vec<String>,
+    /// Pages nested below this one
+    children: Vec<SourcePage>,
+}
+
+/// Counters reported at the end of a sync run.
+#[derive(Debug, Default)]
+struct SyncSummary {
+    created: usize,
+    updated: usize,
+    unchanged: usize,
+    archived: usize,
+    conflicts: usize,
+}
+
+/// A page below the parent that was created by sync.
+struct SyncedPage {
+    /// The page, with its version
+    page: ConfluencePage,
+    /// Value of the sync property
+    stored: Value,
+}
+
+/// State shared while walking the source tree.
+struct Syncer<'a> {
+    client: &'a ConfluenceClient,
+    space: &'a str,
+    dry_run: bool,
+    verbose: bool,
+    summary: SyncSummary,
+    sources: HashSet<String>,
+    /// Synced pages below the parent, by source path
+    synced: HashMap<String, SyncedPage>,
+}
+
+/// Execute the sync command.
+pub fn run(
+    cmd: &SyncCmd,
+    dry_run: bool,
+    _pretty: bool,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+    match &cmd.operation {
+        SyncOp::Push {
+            dir,
+            space,
+            parent,
+            prune,
+        } => {
+            if verbose {
+                eprintln!(
+                    "Syncing {} into space {space} under page {parent}",
+                    dir.display()
+                );
+            }
+            if !dir.is_dir() {
+                return Err(format!("Not a directory: {}", dir.display()).into());
+            }
+
+            let pages = load_directory(dir, dir)?;
+            if pages.is_empty() {
+                println!("No Markdown files found in {}", dir.display());
+                return Ok(());
+            }
+
+            let client = create_confluence_client()?;
+            let synced = load_synced_pages(&client, parent)?;
+            if verbose {
+                eprintln!("Found {} synced pages under page {parent}", synced.len());
+            }
+            let mut syncer = Syncer {
+                client: &client,
+                space,
+                dry_run,
+                verbose,
+                summary: SyncSummary::default(),
+                sources: HashSet::new(),
+                synced,
+            };
+
+            for page in &pages {
+                syncer.sync_page(page, Some(parent), "", 0)?;
+            }
+
+            if *prune {
+                syncer.prune()?;
+            }
+
+            let summary = &syncer.summary;
+            let prefix = if dry_run {
+                "DRY RUN: Would sync"
+            } else {
+                "Synced"
+            };
+            println!(
+                "{prefix}: {} created, {} updated, {} unchanged, {} archived",
+                summary.created, summary.updated, summary.unchanged, summary.archived
+            );
+            if summary.conflicts > 0 {
+                return Err(format!(
+                    "{} pages were skipped because a page with the same title was not created by sync",
+                    summary.conflicts
+                )
+                .into());
+            }
+        }
+    }
+    Ok(())
+}
+
+impl Syncer<'_> {
+    /// Create or update a page and then recurse into its children.
+    fn sync_page(
+        &mut self,
+        page: &SourcePage,
+        parent_id: Option<&str>,
+        parent_source: &str,
+        depth: usize,
+    ) -> Result<(), Box<dyn Error>> {
+        let hash = content_hash(page, parent_source);
+        self.sources.insert(page.source.clone());
+
+        let page_id = match self.synced.get(&page.source) {
+            Some(SyncedPage {
+                page: existing,
+                stored,
+            }) => {
+                if stored.get("hash").and_then(|h| h.as_str()) == Some(hash.as_str()) {
+                    self.report("unchanged", page, depth);
+                    self.summary.unchanged += 1;
+                } else {
+                    self.report("update", page, depth);
+                    self.summary.updated += 1;
+                    if !self.dry_run {
+                        let version = existing.version.as_ref().map_or(1, |v| v.number) + 1;
+                        self.client.update_page(
+                            &existing.id,
+                            &page.title,
+                            &StorageBody::storage(&page.body),
+                            version,
+                            parent_id,
+                        )?;
+                        let previous_labels: Vec<String> = stored
+                            .get("labels")
+                            .and_then(|l| serde_json::from_value(l.clone()).ok())
+                            .unwrap_or_default();
+                        self.finish_page(&existing.id, page, &hash, &previous_labels)?;
+                    }
+                }
+                Some(existing.id.clone())
+            }
+            None => {
+                // Titles are unique within a space, so a page with this title
+                // that sync did not create for this source blocks the file.
+                if let Some(other) = self.client.find_page_by_title(self.space, &page.title)? {
+                    self.report("conflict", page, depth);
+                    eprintln!(
+                        "Page '{}' ({}) already exists and was not synced from {}; skipping it and its children",
+                        other.title, other.id, page.source
+                    );
+                    self.summary.conflicts += 1;
+                    // The files still exist, so keep --prune away from their pages.
+                    self.mark_sources(&page.children);
+                    return Ok(());
+                }
+
+                self.report("create", page, depth);
+                self.summary.created += 1;
+                if self.dry_run {
+                    None
+                } else {
+                    let created = self.client.create_page(
+                        self.space,
+                        parent_id,
+                        &page.title,
+                        &StorageBody::storage(&page.body),
+                    )?;
+                    self.finish_page(&created.id, page, &hash, &[])?;
+                    Some(created.id)
+                }
+            }
+        };
+
+        for child in &page.children {
+            self.sync_page(child, page_id.as_deref(), &page.source, depth + 1)?;
+        }
+
+        Ok(())
+    }
+
+    /// Record source pages as present without syncing them.
+    fn mark_sources(&mut self, pages: &[SourcePage]) {
+        for page in pages {
+            self.sources.insert(page.source.clone());
+            self.mark_sources(&page.children);
+        }
+    }
+
+    /// Apply labels and record the sync property after a page was written.
+    fn finish_page(
+        &self,
+        page_id: &str,
+        page: &SourcePage,
+        hash: &str,
+        previous_labels: &[String],
+    ) -> Result<(), Box<dyn Error>> {
+        let stale: Vec<&str> = previous_labels
+            .iter()
+            .filter(|l| !page.labels.contains(l))
+            .map(|l| l.as_str())
+            .collect();
+        if !stale.is_empty() {
+            self.client.remove_page_labels(page_id, &stale)?;
+        }
+
+        if !page.labels.is_empty() {
+            let labels: Vec<&str> = page.labels.iter().map(|l| l.as_str()).collect();
+            self.client.add_page_labels(page_id, &labels)?;
+        }
+
+        self.client.set_content_property(
+            page_id,
+            SYNC_PROPERTY_KEY,
+            serde_json::json!({
+                "hash": hash,
+                "source": page.source,
+                "labels": page.labels,
+            }),
+        )?;
+        Ok(())
+    }
+
+    /// Archive synced pages below the parent whose source no longer exists.
+    ///
+    /// Only pages carrying the sync property are considered; hand-written
+    /// pages are never touched.
+    fn prune(&mut self) -> Result<(), Box<dyn Error>> {
+        let mut stale = Vec::new();
+
+        for (source, synced) in &self.synced {
+            if !self.sources.contains(source) {
+                let prefix = if self.dry_run { "DRY RUN: " } else { "" };
+                println!("{prefix}{:<10}{} ({source})", "archive", synced.page.title);
+                stale.push(synced.page.id.clone());
+            }
+        }
+
+        self.summary.archived = stale.len();
+        if !self.dry_run && !stale.is_empty() {
+            let ids: Vec<&str> = stale.iter().map(|id| id.as_str()).collect();
+            self.client.archive_pages(&ids)?;
+        }
+        Ok(())
+    }
+
+    /// Print one line of the sync plan, indented to show the page hierarchy.
+    fn report(&self, action: &str, page: &SourcePage, depth: usize) {
+        if action == "unchanged" && !self.verbose && !self.dry_run {
+            return;
+        }
+        let prefix = if self.dry_run { "DRY RUN: " } else { "" };
+        let indent = "    ".repeat(depth);
+        println!(
+            "{prefix}{action:<10}{indent}{} ({})",
+            page.title, page.source
+        );
+    }
+}
+
+/// Load the pages below the parent that carry the sync property, by source.
+fn load_synced_pages(
+    client: &ConfluenceClient,
+    parent_id: &str,
+) -> Result<HashMap<String, SyncedPage>, Box<dyn Error>> {
+    let mut synced = HashMap::new();
+    let cql = format!("ancestor = {parent_id} and type = page");
+    for page in client.query_all_pages_by_cql_with_expand(&cql, "version")? {
+        let Some(property) = client.get_content_property(&page.id, SYNC_PROPERTY_KEY)? else {
+            continue;
+        };
+        let Some(source) = property.value.get("source").and_then(|s| s.as_str()) else {
+            continue;
+        };
+        synced.insert(
+            source.to_string(),
+            SyncedPage {
+                page,
+                stored: property.value,
+            },
+        );
+    }
+    Ok(synced)
+}
+
+/// Recursively load Markdown files and sub-directories as source pages.
+fn load_directory(root: &Path, dir: &Path) -> Result<Vec<SourcePage>, Box<dyn Error>> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+
+    let mut pages = Vec::new();
+    for path in entries {
+        let name = file_name(&path);
+        if name.starts_with('.') {
+            continue;
+        }
+
+        if path.is_dir() {
+            let children = load_directory(root, &path)?;
+            let index = INDEX_FILES
+                .iter()
+                .map(|f| path.join(f))
+                .find(|p| p.is_file());
+            if children.is_empty() && index.is_none() {
+                continue;
+            }
+
+            let mut page = match index {
+                Some(index) => load_markdown(root, &index)?,
+                None => SourcePage {
+                    source: relative_source(root, &path),
+                    title: name.to_string(),
+                    body: String::new(),
+                    labels: Vec::new(),
+                    children: Vec::new(),
+                },
+            };
+            page.children = children;
+            pages.push(page);
+        } else if is_markdown(&path) && !(dir != root && INDEX_FILES.contains(&name)) {
+            pages.push(load_markdown(root, &path)?);
+        }
+    }
+
+    Ok(pages)
+}
+
+/// Load a single Markdown file, applying its front matter.
+fn load_markdown(root: &Path, path: &Path) -> Result<SourcePage, Box<dyn Error>> {
+    let content = fs::read_to_string(path)?;
+    let (front_matter, markdown) = split_front_matter(&content);
+    let front_matter: FrontMatter = match front_matter {
+        Some(yaml) => serde_yaml::from_str(yaml)
+            .map_err(|e| format!("Invalid front matter in {}: {e}", path.display()))?,
+        None => FrontMatter::default(),
+    };
+
+    // Use a leading H1 as the title when front matter does not set one, and
+    // drop it from the body so it is not rendered twice.
+    let mut body = markdown;
+    let heading = markdown
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .and_then(|line| line.strip_prefix("# "))
+        .map(|h| h.trim().to_string());
+    if front_matter.title.is_none() && heading.is_some() {
+        let start = markdown.find("# ").unwrap_or(0);
+        body = markdown[start..]
+            .split_once('\n')
+            .map_or("", |(_, rest)| rest);
+    }
+
+    let title = front_matter.title.or(heading).unwrap_or_else(|| {
+        path.file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default()
+            .to_string()
+    });
+
+    Ok(SourcePage {
+        source: relative_source(root, path),
+        title,
+        body: markdown_to_storage(body),
+        labels: front_matter.labels,
+        children: Vec::new(),
+    })
+}
+
+/// Split YAML front matter delimited by `---` lines from the Markdown body.
+fn split_front_matter(content: &str) -> (Option<&str>, &str) {
+    let Some(rest) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return (None, content);
+    };
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    (None, content)
+}
+
+/// Hash everything that ends up on the page, so unchanged pages can be skipped.
+fn content_hash(page: &SourcePage, parent_source: &str) -> String {
+    let mut labels = page.labels.clone();
+    labels.sort();
+
+    let mut hasher = Sha256::new();
+    for part in [
+        page.title.as_str(),
+        page.body.as_str(),
+        &labels.join(","),
+        parent_source,
+    ] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Path relative to the sync root, with forward slashes on every platform.
+fn relative_source(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn file_name(path: &Path) -> &str {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default()
+}
+
+fn is_markdown(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("md") | Some("markdown")
+    )
+}
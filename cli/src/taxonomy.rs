@@ -0,0 +1,166 @@
+//! Approved label vocabulary for `ctag lint`.
+//!
+//! A taxonomy file lists the allowed labels, aliases that should be rewritten
+//! to a canonical label, and labels every page in a space must carry. It may
+//! be written in YAML or JSON:
+//!
+//! ```yaml
+//! allowed: [runbook, how-to, policy, ops]
+//! aliases:
+//!   howto: how-to
+//!   run-book: runbook
+//! required:
+//!   OPS: [ops]
+//! ```
+
+use nix_rust_template::ConfluencePage;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// An approved label vocabulary.
+#[derive(Debug, Deserialize)]
+pub struct Taxonomy {
+    /// Labels that may be used
+    #[serde(default)]
+    pub allowed: BTreeSet<String>,
+    /// Alias -> canonical label name
+    #[serde(default)]
+    pub aliases: HashMap<String, String>,
+    /// Space key -> labels every page in that space must carry
+    #[serde(default)]
+    pub required: HashMap<String, Vec<String>>,
+}
+
+/// Problems found on one page.
+#[derive(Debug, Default, Serialize)]
+pub struct PageFindings {
+    /// Page ID
+    pub id: String,
+    /// Page title
+    pub title: String,
+    /// Labels that are not in the vocabulary
+    pub unapproved: Vec<UnapprovedLabel>,
+    /// Required labels the page does not carry
+    pub missing: Vec<String>,
+}
+
+/// A label that is not in the vocabulary.
+#[derive(Debug, Serialize)]
+pub struct UnapprovedLabel {
+    /// The label
+    pub label: String,
+    /// Canonical name, if the label is a known alias
+    pub canonical: Option<String>,
+}
+
+/// Two labels that are probably spellings of the same thing.
+#[derive(Debug, Serialize)]
+pub struct NearDuplicate {
+    /// First label
+    pub a: String,
+    /// Second label
+    pub b: String,
+    /// Edit distance between them
+    pub distance: usize,
+}
+
+/// Result of linting a set of pages.
+#[derive(Debug, Serialize)]
+pub struct LintReport {
+    /// Number of pages inspected
+    pub pages: usize,
+    /// Pages with unapproved or missing labels
+    pub findings: Vec<PageFindings>,
+    /// Near-duplicate labels in use
+    pub near_duplicates: Vec<NearDuplicate>,
+}
+
+impl Taxonomy {
+    /// Read a YAML or JSON taxonomy file.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let text = fs::read_to_string(path)?;
+        let taxonomy: Taxonomy = serde_yaml::from_str(&text)
+            .map_err(|e| format!("Invalid taxonomy {}: {e}", path.display()))?;
+        Ok(taxonomy)
+    }
+
+    /// Check pages against the vocabulary.
+    ///
+    /// Labels within `max_distance` edits of each other are reported as near
+    /// duplicates, unless they are already declared as alias and canonical.
+    pub fn lint(&self, pages: &[ConfluencePage], max_distance: usize) -> LintReport {
+        let mut findings = Vec::new();
+        let mut in_use: BTreeSet<String> = BTreeSet::new();
+
+        for page in pages {
+            let labels = page_labels(page);
+            let mut page_findings = PageFindings {
+                id: page.id.clone(),
+                title: page.title.clone(),
+                ..PageFindings::default()
+            };
+
+            for label in &labels {
+                in_use.insert(label.clone());
+                if !self.allowed.contains(label) {
+                    page_findings.unapproved.push(UnapprovedLabel {
+                        label: label.clone(),
+                        canonical: self.aliases.get(label).cloned(),
+                    });
+                }
+            }
+
+            let space_key = page.space.as_ref().map(|s| s.key.as_str());
+            if let Some(required) = space_key.and_then(|k| self.required.get(k)) {
+                page_findings.missing = required
+                    .iter()
+                    .filter(|r| !labels.contains(r))
+                    .cloned()
+                    .collect();
+            }
+
+            if !page_findings.unapproved.is_empty() || !page_findings.missing.is_empty() {
+                findings.push(page_findings);
+            }
+        }
+
+        let candidates: Vec<&String> = in_use.union(&self.allowed).collect();
+        let mut near_duplicates = Vec::new();
+        for (i, a) in candidates.iter().enumerate() {
+            for b in &candidates[i + 1..] {
+                if !in_use.contains(*a) && !in_use.contains(*b) {
+                    continue;
+                }
+                if self.aliases.get(*a) == Some(*b) || self.aliases.get(*b) == Some(*a) {
+                    continue;
+                }
+                let distance = strsim::levenshtein(a, b);
+                if distance <= max_distance {
+                    near_duplicates.push(NearDuplicate {
+                        a: a.to_string(),
+                        b: b.to_string(),
+                        distance,
+                    });
+                }
+            }
+        }
+
+        LintReport {
+            pages: pages.len(),
+            findings,
+            near_duplicates,
+        }
+    }
+}
+
+/// Labels on a page.
+fn page_labels(page: &ConfluencePage) -> Vec<String> {
+    page.metadata
+        .as_ref()
+        .and_then(|m| m.labels.as_ref())
+        .map(|labels| labels.results.iter().map(|l| l.name.clone()).collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,1131 @@
+//! Atlassian Document Format (ADF) and its conversion to and from Markdown.
+//!
+//! Jira descriptions and comments are ADF documents: JSON trees of block
+//! and inline nodes. [`adf_to_markdown`] renders them for a terminal and
+//! [`markdown_to_adf`] builds them from text typed by a person.
+//!
+//! ADF nodes that Markdown has no syntax for are written so that they
+//! survive a round trip:
+//!
+//! - mentions as links with a `mention:` scheme: `[@Jane Doe](mention:ACCOUNT_ID)`
+//! - status lozenges as links with a `status:` scheme: `[In Progress](status:blue)`
+//! - panels as GitHub alerts: info, success, note, warning and error panels
+//!   are `> [!NOTE]`, `> [!TIP]`, `> [!IMPORTANT]`, `> [!WARNING]` and
+//!   `> [!CAUTION]` block quotes
+
+use pulldown_cmark::{BlockQuoteKind, CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// An ADF document, the root of a description or comment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "doc")]
+pub struct AdfDocument {
+    /// Format version, always 1
+    pub version: u32,
+    /// Top-level block nodes
+    #[serde(default)]
+    pub content: Vec<AdfNode>,
+}
+
+/// A block or inline ADF node.
+///
+/// Nodes this crate does not model deserialize as [`AdfNode::Unsupported`]
+/// and render as nothing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum AdfNode {
+    /// Paragraph of inline nodes
+    Paragraph {
+        #[serde(default)]
+        content: Vec<AdfNode>,
+    },
+    /// Heading of inline nodes
+    Heading {
+        attrs: HeadingAttrs,
+        #[serde(default)]
+        content: Vec<AdfNode>,
+    },
+    /// Bulleted list of list items
+    BulletList {
+        #[serde(default)]
+        content: Vec<AdfNode>,
+    },
+    /// Numbered list of list items
+    OrderedList {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        attrs: Option<OrderedListAttrs>,
+        #[serde(default)]
+        content: Vec<AdfNode>,
+    },
+    /// Item of a bulleted or numbered list, holding blocks
+    ListItem {
+        #[serde(default)]
+        content: Vec<AdfNode>,
+    },
+    /// Checklist of task items
+    TaskList {
+        attrs: LocalIdAttrs,
+        #[serde(default)]
+        content: Vec<AdfNode>,
+    },
+    /// Checklist item, holding inline nodes
+    TaskItem {
+        attrs: TaskItemAttrs,
+        #[serde(default)]
+        content: Vec<AdfNode>,
+    },
+    /// Block quote
+    Blockquote {
+        #[serde(default)]
+        content: Vec<AdfNode>,
+    },
+    /// Code block holding a single text node
+    CodeBlock {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        attrs: Option<CodeBlockAttrs>,
+        #[serde(default)]
+        content: Vec<AdfNode>,
+    },
+    /// Coloured panel (info, note, success, warning or error)
+    Panel {
+        attrs: PanelAttrs,
+        #[serde(default)]
+        content: Vec<AdfNode>,
+    },
+    /// Table of rows
+    Table {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        attrs: Option<Value>,
+        #[serde(default)]
+        content: Vec<AdfNode>,
+    },
+    /// Table row of header or data cells
+    TableRow {
+        #[serde(default)]
+        content: Vec<AdfNode>,
+    },
+    /// Header cell, holding blocks
+    TableHeader {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        attrs: Option<Value>,
+        #[serde(default)]
+        content: Vec<AdfNode>,
+    },
+    /// Data cell, holding blocks
+    TableCell {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        attrs: Option<Value>,
+        #[serde(default)]
+        content: Vec<AdfNode>,
+    },
+    /// Horizontal rule
+    Rule,
+    /// Collapsible section with a title
+    Expand {
+        #[serde(default)]
+        attrs: ExpandAttrs,
+        #[serde(default)]
+        content: Vec<AdfNode>,
+    },
+    /// Collapsible section inside a table cell
+    NestedExpand {
+        #[serde(default)]
+        attrs: ExpandAttrs,
+        #[serde(default)]
+        content: Vec<AdfNode>,
+    },
+    /// Row of layout columns
+    LayoutSection {
+        #[serde(default)]
+        content: Vec<AdfNode>,
+    },
+    /// Layout column, holding blocks
+    LayoutColumn {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        attrs: Option<Value>,
+        #[serde(default)]
+        content: Vec<AdfNode>,
+    },
+    /// Wrapper around a single attachment
+    MediaSingle {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        attrs: Option<Value>,
+        #[serde(default)]
+        content: Vec<AdfNode>,
+    },
+    /// Group of attachments
+    MediaGroup {
+        #[serde(default)]
+        content: Vec<AdfNode>,
+    },
+    /// An attachment; the attributes are kept as Jira sent them
+    Media { attrs: Value },
+    /// Smart link shown as a card
+    BlockCard { attrs: CardAttrs },
+    /// Text with optional formatting marks
+    Text {
+        text: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        marks: Vec<AdfMark>,
+    },
+    /// Line break inside a paragraph
+    HardBreak,
+    /// Mention of a user
+    Mention { attrs: MentionAttrs },
+    /// Status lozenge
+    Status { attrs: StatusAttrs },
+    /// Emoji
+    Emoji { attrs: EmojiAttrs },
+    /// Inline smart link
+    InlineCard { attrs: CardAttrs },
+    /// Date, as a Unix timestamp in milliseconds
+    Date { attrs: DateAttrs },
+    /// Any node this crate does not model
+    #[serde(other)]
+    Unsupported,
+}
+
+/// Formatting applied to a text node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum AdfMark {
+    /// Bold
+    Strong,
+    /// Italic
+    Em,
+    /// Inline code
+    Code,
+    /// Strikethrough
+    Strike,
+    /// Underline, which Markdown cannot express
+    Underline,
+    /// Hyperlink
+    Link { attrs: LinkAttrs },
+    /// Any mark this crate does not model, such as text colour
+    #[serde(other)]
+    Unsupported,
+}
+
+/// Attributes of a heading.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HeadingAttrs {
+    /// Heading level, 1 to 6
+    pub level: u8,
+}
+
+/// Attributes of a numbered list.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderedListAttrs {
+    /// Number of the first item
+    pub order: u32,
+}
+
+/// Attributes of a node that only carries a local ID.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocalIdAttrs {
+    /// ID unique within the document
+    #[serde(rename = "localId")]
+    pub local_id: String,
+}
+
+/// Attributes of a checklist item.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskItemAttrs {
+    /// ID unique within the document
+    #[serde(rename = "localId")]
+    pub local_id: String,
+    /// "TODO" or "DONE"
+    pub state: String,
+}
+
+/// Attributes of a code block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CodeBlockAttrs {
+    /// Language used for highlighting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+/// Attributes of a panel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PanelAttrs {
+    /// Panel type (e.g., "info" or "warning")
+    #[serde(rename = "panelType")]
+    pub panel_type: String,
+}
+
+/// Attributes of an expand.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExpandAttrs {
+    /// Title shown while collapsed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+/// Attributes of a smart link.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CardAttrs {
+    /// Linked URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Attributes of a mention.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MentionAttrs {
+    /// Account ID of the mentioned user
+    pub id: String,
+    /// Display text, usually "@" followed by the user's name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+/// Attributes of a status lozenge.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusAttrs {
+    /// Lozenge text
+    pub text: String,
+    /// Colour: neutral, purple, blue, red, yellow or green
+    pub color: String,
+}
+
+/// Attributes of an emoji.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmojiAttrs {
+    /// Short name (e.g., ":smile:")
+    #[serde(rename = "shortName")]
+    pub short_name: String,
+    /// Unicode text of the emoji, when it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+/// Attributes of a date.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DateAttrs {
+    /// Unix timestamp in milliseconds, as a string
+    pub timestamp: String,
+}
+
+/// Attributes of a link mark.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LinkAttrs {
+    /// Link target
+    pub href: String,
+}
+
+/// Render an ADF document as Markdown.
+///
+/// Plain strings, as returned by older API versions, are returned as they
+/// are; values that are not ADF are shown as JSON.
+pub fn adf_to_markdown(doc: &Value) -> String {
+    match doc {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        _ => match serde_json::from_value::<AdfDocument>(doc.clone()) {
+            Ok(doc) => doc.to_markdown(),
+            Err(_) => doc.to_string(),
+        },
+    }
+}
+
+/// Build an ADF document from Markdown.
+pub fn markdown_to_adf(markdown: &str) -> Value {
+    AdfDocument::from_markdown(markdown).to_value()
+}
+
+impl AdfDocument {
+    /// Render the document as Markdown.
+    pub fn to_markdown(&self) -> String {
+        blocks_to_markdown(&self.content)
+    }
+
+    /// Build a document from Markdown.
+    pub fn from_markdown(markdown: &str) -> Self {
+        let options = Options::ENABLE_TABLES
+            | Options::ENABLE_STRIKETHROUGH
+            | Options::ENABLE_TASKLISTS
+            | Options::ENABLE_GFM;
+        let mut builder = Builder::new();
+        for event in Parser::new_ext(markdown, options) {
+            builder.event(event);
+        }
+        AdfDocument {
+            version: 1,
+            content: builder.finish(),
+        }
+    }
+
+    /// The document as JSON, ready to send to Jira.
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap_or(Value::Null)
+    }
+}
+
+impl AdfNode {
+    /// Whether the node goes inside a paragraph rather than between blocks.
+    fn is_inline(&self) -> bool {
+        matches!(
+            self,
+            AdfNode::Text { .. }
+                | AdfNode::HardBreak
+                | AdfNode::Mention { .. }
+                | AdfNode::Status { .. }
+                | AdfNode::Emoji { .. }
+                | AdfNode::InlineCard { .. }
+                | AdfNode::Date { .. }
+        )
+    }
+}
+
+/// Render block nodes separated by blank lines.
+fn blocks_to_markdown(nodes: &[AdfNode]) -> String {
+    nodes
+        .iter()
+        .map(block_to_markdown)
+        .filter(|block| !block.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Render a single block node.
+fn block_to_markdown(node: &AdfNode) -> String {
+    match node {
+        AdfNode::Paragraph { content } => inline_to_markdown(content),
+        AdfNode::Heading { attrs, content } => format!(
+            "{} {}",
+            "#".repeat(attrs.level.clamp(1, 6).into()),
+            inline_to_markdown(content)
+        ),
+        AdfNode::BulletList { content } | AdfNode::TaskList { content, .. } => {
+            list_to_markdown(content, |_| "- ".to_string())
+        }
+        AdfNode::OrderedList { attrs, content } => {
+            let start = attrs.as_ref().map_or(1, |a| a.order);
+            list_to_markdown(content, |i| format!("{}. ", start as usize + i))
+        }
+        AdfNode::Blockquote { content } => prefix_lines(&blocks_to_markdown(content), "> "),
+        AdfNode::Panel { attrs, content } => prefix_lines(
+            &format!(
+                "[!{}]\n{}",
+                alert_kind(&attrs.panel_type),
+                blocks_to_markdown(content)
+            ),
+            "> ",
+        ),
+        AdfNode::CodeBlock { attrs, content } => {
+            let code = plain_text(content);
+            let fence = "`".repeat(longest_run(&code, '`').max(2) + 1);
+            let language = attrs
+                .as_ref()
+                .and_then(|a| a.language.as_deref())
+                .unwrap_or_default();
+            format!("{fence}{language}\n{code}\n{fence}")
+        }
+        AdfNode::Table { content, .. } => table_to_markdown(content),
+        AdfNode::Rule => "---".to_string(),
+        AdfNode::Expand { attrs, content } | AdfNode::NestedExpand { attrs, content } => {
+            let body = blocks_to_markdown(content);
+            match attrs.title.as_deref().filter(|t| !t.is_empty()) {
+                Some(title) => format!("**{}**\n\n{body}", escape(title)),
+                None => body,
+            }
+        }
+        AdfNode::LayoutSection { content }
+        | AdfNode::LayoutColumn { content, .. }
+        | AdfNode::MediaSingle { content, .. }
+        | AdfNode::MediaGroup { content }
+        | AdfNode::ListItem { content }
+        | AdfNode::TableRow { content }
+        | AdfNode::TableHeader { content, .. }
+        | AdfNode::TableCell { content, .. } => blocks_to_markdown(content),
+        AdfNode::Media { attrs } => match attrs["alt"].as_str().filter(|a| !a.is_empty()) {
+            Some(alt) => format!("[attachment: {alt}]"),
+            None => "[attachment]".to_string(),
+        },
+        AdfNode::BlockCard { attrs } => attrs
+            .url
+            .as_ref()
+            .map(|url| format!("<{url}>"))
+            .unwrap_or_default(),
+        AdfNode::TaskItem { content, .. } => inline_to_markdown(content),
+        AdfNode::Unsupported => String::new(),
+        inline => inline_to_markdown(std::slice::from_ref(inline)),
+    }
+}
+
+/// Render list items, each introduced by the marker for its position.
+fn list_to_markdown(items: &[AdfNode], marker: impl Fn(usize) -> String) -> String {
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let (marker, body) = match item {
+                AdfNode::ListItem { content } => (
+                    marker(i),
+                    content
+                        .iter()
+                        .map(block_to_markdown)
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                ),
+                AdfNode::TaskItem { attrs, content } => {
+                    let check = if attrs.state == "DONE" { 'x' } else { ' ' };
+                    (format!("- [{check}] "), inline_to_markdown(content))
+                }
+                // Nested checklists sit directly in their parent list.
+                nested => (
+                    String::new(),
+                    prefix_lines(&block_to_markdown(nested), "  "),
+                ),
+            };
+            let indent = " ".repeat(marker.len());
+            let mut lines = body.lines();
+            let mut out = format!("{marker}{}", lines.next().unwrap_or_default());
+            for line in lines {
+                out.push('\n');
+                if !line.is_empty() {
+                    out.push_str(&indent);
+                    out.push_str(line);
+                }
+            }
+            out
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render table rows as a GitHub table; the first row is the header.
+fn table_to_markdown(rows: &[AdfNode]) -> String {
+    let rows: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| match row {
+            AdfNode::TableRow { content } => content
+                .iter()
+                .map(|cell| {
+                    block_to_markdown(cell)
+                        .split('\n')
+                        .map(str::trim_end)
+                        .filter(|line| !line.is_empty())
+                        .collect::<Vec<_>>()
+                        .join("<br>")
+                        .replace('|', "\\|")
+                })
+                .collect(),
+            _ => Vec::new(),
+        })
+        .collect();
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    if columns == 0 {
+        return String::new();
+    }
+
+    let line = |cells: &[String]| {
+        let mut out = String::from("|");
+        for i in 0..columns {
+            out.push_str(&format!(
+                " {} |",
+                cells.get(i).map(String::as_str).unwrap_or_default()
+            ));
+        }
+        out
+    };
+    let mut lines = vec![line(&rows[0]), format!("|{}", " --- |".repeat(columns))];
+    lines.extend(rows[1..].iter().map(|row| line(row)));
+    lines.join("\n")
+}
+
+/// Render inline nodes.
+fn inline_to_markdown(nodes: &[AdfNode]) -> String {
+    let mut out = String::new();
+    let mut i = 0;
+    while i < nodes.len() {
+        match &nodes[i] {
+            AdfNode::Text { marks, .. } => {
+                // Adjacent text with the same marks is rendered as one run so
+                // that delimiters are not doubled up between the pieces.
+                let mut text = String::new();
+                while let Some(AdfNode::Text { text: t, marks: m }) = nodes.get(i) {
+                    if m != marks {
+                        break;
+                    }
+                    text.push_str(t);
+                    i += 1;
+                }
+                out.push_str(&marked_text(&text, marks));
+                continue;
+            }
+            AdfNode::HardBreak => out.push_str("  \n"),
+            AdfNode::Mention { attrs } => {
+                let text = attrs
+                    .text
+                    .clone()
+                    .unwrap_or_else(|| format!("@{}", attrs.id));
+                out.push_str(&format!("[{}](mention:{})", escape(&text), attrs.id));
+            }
+            AdfNode::Status { attrs } => {
+                out.push_str(&format!(
+                    "[{}](status:{})",
+                    escape(&attrs.text),
+                    attrs.color
+                ));
+            }
+            AdfNode::Emoji { attrs } => {
+                out.push_str(attrs.text.as_deref().unwrap_or(&attrs.short_name));
+            }
+            AdfNode::InlineCard { attrs } => {
+                if let Some(url) = &attrs.url {
+                    out.push_str(&format!("<{url}>"));
+                }
+            }
+            AdfNode::Date { attrs } => out.push_str(&format_date(&attrs.timestamp)),
+            block => out.push_str(&block_to_markdown(block)),
+        }
+        i += 1;
+    }
+    out
+}
+
+/// Wrap text in the Markdown for its marks.
+fn marked_text(text: &str, marks: &[AdfMark]) -> String {
+    // Delimiters must hug the text, so surrounding spaces stay outside them.
+    let core = text.trim();
+    if core.is_empty() {
+        return text.to_string();
+    }
+    let lead = &text[..text.len() - text.trim_start().len()];
+    let trail = &text[text.trim_end().len()..];
+
+    let mut out = if marks.contains(&AdfMark::Code) {
+        let ticks = "`".repeat(longest_run(core, '`') + 1);
+        let pad = if core.starts_with('`') || core.ends_with('`') {
+            " "
+        } else {
+            ""
+        };
+        format!("{ticks}{pad}{core}{pad}{ticks}")
+    } else {
+        escape(core)
+    };
+    for mark in marks {
+        out = match mark {
+            AdfMark::Strong => format!("**{out}**"),
+            AdfMark::Em => format!("*{out}*"),
+            AdfMark::Strike => format!("~~{out}~~"),
+            _ => out,
+        };
+    }
+    for mark in marks {
+        if let AdfMark::Link { attrs } = mark {
+            out = format!("[{out}]({})", attrs.href);
+        }
+    }
+    format!("{lead}{out}{trail}")
+}
+
+/// Escape characters that would otherwise start emphasis or code.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '*' | '`') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Prefix every line, leaving the prefix's trailing space off blank lines.
+fn prefix_lines(text: &str, prefix: &str) -> String {
+    text.lines()
+        .map(|line| {
+            if line.is_empty() {
+                prefix.trim_end().to_string()
+            } else {
+                format!("{prefix}{line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Concatenated text of text nodes, ignoring marks.
+fn plain_text(nodes: &[AdfNode]) -> String {
+    nodes
+        .iter()
+        .map(|node| match node {
+            AdfNode::Text { text, .. } => text.clone(),
+            AdfNode::Mention { attrs } => attrs.text.clone().unwrap_or_default(),
+            AdfNode::Status { attrs } => attrs.text.clone(),
+            AdfNode::HardBreak => "\n".to_string(),
+            _ => String::new(),
+        })
+        .collect()
+}
+
+/// Length of the longest run of `c` in `text`.
+fn longest_run(text: &str, c: char) -> usize {
+    let (mut longest, mut current) = (0, 0);
+    for ch in text.chars() {
+        current = if ch == c { current + 1 } else { 0 };
+        longest = longest.max(current);
+    }
+    longest
+}
+
+/// GitHub alert kind used for a panel type.
+fn alert_kind(panel_type: &str) -> &'static str {
+    match panel_type {
+        "success" => "TIP",
+        "note" => "IMPORTANT",
+        "warning" => "WARNING",
+        "error" => "CAUTION",
+        _ => "NOTE",
+    }
+}
+
+/// Panel type written as a GitHub alert kind.
+fn panel_type(kind: BlockQuoteKind) -> &'static str {
+    match kind {
+        BlockQuoteKind::Note => "info",
+        BlockQuoteKind::Tip => "success",
+        BlockQuoteKind::Important => "note",
+        BlockQuoteKind::Warning => "warning",
+        BlockQuoteKind::Caution => "error",
+    }
+}
+
+/// Format a millisecond Unix timestamp as a calendar date (YYYY-MM-DD).
+fn format_date(timestamp: &str) -> String {
+    let Ok(millis) = timestamp.parse::<i64>() else {
+        return timestamp.to_string();
+    };
+    // Days since the epoch to a civil date, after Howard Hinnant's algorithm.
+    let z = millis.div_euclid(86_400_000) + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// An open Markdown container while building a document.
+enum Frame {
+    Root,
+    Paragraph,
+    Heading(u8),
+    BlockQuote(Option<BlockQuoteKind>),
+    CodeBlock(Option<String>, String),
+    List(Option<u64>),
+    Item(Option<bool>),
+    Table,
+    TableHead,
+    TableRow,
+    TableCell,
+    /// Link with a `mention:` or `status:` target
+    Special(String),
+    /// Container with no ADF equivalent; its children are kept in place
+    Other,
+}
+
+/// Builds ADF nodes from Markdown parser events.
+struct Builder {
+    /// Open containers with the nodes collected in each
+    stack: Vec<(Frame, Vec<AdfNode>)>,
+    /// Marks applied to text at the current position
+    marks: Vec<AdfMark>,
+    /// Whether each open link is a mention or status rather than a link mark
+    links: Vec<bool>,
+    /// Whether the cells being read are header cells
+    in_head: bool,
+    /// Counter for the local IDs checklists require
+    next_id: usize,
+}
+
+impl Builder {
+    fn new() -> Self {
+        Builder {
+            stack: vec![(Frame::Root, Vec::new())],
+            marks: Vec::new(),
+            links: Vec::new(),
+            in_head: false,
+            next_id: 0,
+        }
+    }
+
+    /// Handle one parser event.
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => {
+                if let Some((Frame::CodeBlock(_, code), _)) = self.stack.last_mut() {
+                    code.push_str(&text);
+                } else {
+                    self.text(&text, None);
+                }
+            }
+            Event::Code(text) => self.text(&text, Some(AdfMark::Code)),
+            Event::InlineHtml(html) if is_line_break(&html) => self.push(AdfNode::HardBreak),
+            Event::Html(text)
+            | Event::InlineHtml(text)
+            | Event::InlineMath(text)
+            | Event::DisplayMath(text)
+            | Event::FootnoteReference(text) => self.text(&text, None),
+            Event::SoftBreak => self.text(" ", None),
+            Event::HardBreak => self.push(AdfNode::HardBreak),
+            Event::Rule => self.push(AdfNode::Rule),
+            Event::TaskListMarker(checked) => {
+                if let Some((Frame::Item(task), _)) = self
+                    .stack
+                    .iter_mut()
+                    .rev()
+                    .find(|(frame, _)| matches!(frame, Frame::Item(_)))
+                {
+                    *task = Some(checked);
+                }
+            }
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        let frame = match tag {
+            Tag::Paragraph => Frame::Paragraph,
+            Tag::Heading { level, .. } => Frame::Heading(level as u8),
+            Tag::BlockQuote(kind) => Frame::BlockQuote(kind),
+            Tag::CodeBlock(kind) => {
+                let language = match kind {
+                    CodeBlockKind::Fenced(lang) if !lang.is_empty() => Some(lang.to_string()),
+                    _ => None,
+                };
+                Frame::CodeBlock(language, String::new())
+            }
+            Tag::List(start) => Frame::List(start),
+            Tag::Item => Frame::Item(None),
+            Tag::Table(_) => Frame::Table,
+            Tag::TableHead => {
+                self.in_head = true;
+                Frame::TableHead
+            }
+            Tag::TableRow => Frame::TableRow,
+            Tag::TableCell => Frame::TableCell,
+            Tag::Emphasis => return self.marks.push(AdfMark::Em),
+            Tag::Strong => return self.marks.push(AdfMark::Strong),
+            Tag::Strikethrough => return self.marks.push(AdfMark::Strike),
+            Tag::Link { dest_url, .. } => {
+                let special = dest_url.starts_with("mention:") || dest_url.starts_with("status:");
+                self.links.push(special);
+                if special {
+                    Frame::Special(dest_url.to_string())
+                } else {
+                    return self.marks.push(AdfMark::Link {
+                        attrs: LinkAttrs {
+                            href: dest_url.to_string(),
+                        },
+                    });
+                }
+            }
+            // ADF images must be uploaded attachments, so the alt text links
+            // to the image instead.
+            Tag::Image { dest_url, .. } => {
+                return self.marks.push(AdfMark::Link {
+                    attrs: LinkAttrs {
+                        href: dest_url.to_string(),
+                    },
+                })
+            }
+            _ => Frame::Other,
+        };
+        self.stack.push((frame, Vec::new()));
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough | TagEnd::Image => {
+                self.marks.pop();
+                return;
+            }
+            TagEnd::Link if self.links.pop() != Some(true) => {
+                self.marks.pop();
+                return;
+            }
+            _ => {}
+        }
+
+        let Some((frame, children)) = self.stack.pop() else {
+            return;
+        };
+        match frame {
+            Frame::Root => self.stack.push((Frame::Root, children)),
+            Frame::Paragraph => self.push(AdfNode::Paragraph {
+                content: merge_text(children),
+            }),
+            Frame::Heading(level) => self.push(AdfNode::Heading {
+                attrs: HeadingAttrs { level },
+                content: merge_text(children),
+            }),
+            // ADF blockquotes cannot contain blockquotes, so nested quotes
+            // are flattened into the outer one.
+            Frame::BlockQuote(None) => {
+                let content = children
+                    .into_iter()
+                    .flat_map(|node| match node {
+                        AdfNode::Blockquote { content } => content,
+                        other => vec![other],
+                    })
+                    .collect();
+                self.push(AdfNode::Blockquote {
+                    content: blocks(content),
+                });
+            }
+            Frame::BlockQuote(Some(kind)) => self.push(AdfNode::Panel {
+                attrs: PanelAttrs {
+                    panel_type: panel_type(kind).to_string(),
+                },
+                content: blocks(children),
+            }),
+            Frame::CodeBlock(language, code) => {
+                let code = code.trim_end_matches('\n');
+                let content = if code.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![AdfNode::Text {
+                        text: code.to_string(),
+                        marks: Vec::new(),
+                    }]
+                };
+                self.push(AdfNode::CodeBlock {
+                    attrs: language.map(|language| CodeBlockAttrs {
+                        language: Some(language),
+                    }),
+                    content,
+                });
+            }
+            Frame::Item(None) => self.push(AdfNode::ListItem {
+                content: blocks(children),
+            }),
+            Frame::Item(Some(checked)) => {
+                // Checklist items hold inline nodes; nested checklists follow
+                // the item in the parent list.
+                let (inline, nested): (Vec<_>, Vec<_>) = children
+                    .into_iter()
+                    .flat_map(|node| match node {
+                        AdfNode::Paragraph { content } => content,
+                        other => vec![other],
+                    })
+                    .partition(AdfNode::is_inline);
+                let local_id = self.local_id();
+                self.push(AdfNode::TaskItem {
+                    attrs: TaskItemAttrs {
+                        local_id,
+                        state: if checked { "DONE" } else { "TODO" }.to_string(),
+                    },
+                    content: merge_text(inline),
+                });
+                for node in nested {
+                    self.push(node);
+                }
+            }
+            Frame::List(start) => {
+                let node = self.list(start, children);
+                self.push(node);
+            }
+            Frame::Table => self.push(AdfNode::Table {
+                attrs: None,
+                content: children,
+            }),
+            Frame::TableHead => {
+                self.in_head = false;
+                self.push(AdfNode::TableRow { content: children });
+            }
+            Frame::TableRow => self.push(AdfNode::TableRow { content: children }),
+            Frame::TableCell => {
+                let content = blocks(children);
+                self.push(if self.in_head {
+                    AdfNode::TableHeader {
+                        attrs: None,
+                        content,
+                    }
+                } else {
+                    AdfNode::TableCell {
+                        attrs: None,
+                        content,
+                    }
+                });
+            }
+            Frame::Special(target) => {
+                let text = plain_text(&children);
+                let node = match target.split_once(':') {
+                    Some(("mention", id)) => AdfNode::Mention {
+                        attrs: MentionAttrs {
+                            id: id.to_string(),
+                            text: Some(text),
+                        },
+                    },
+                    Some((_, color)) => AdfNode::Status {
+                        attrs: StatusAttrs {
+                            text,
+                            color: color.to_string(),
+                        },
+                    },
+                    None => return,
+                };
+                self.push(node);
+            }
+            Frame::Other => {
+                for node in children {
+                    self.push(node);
+                }
+            }
+        }
+    }
+
+    /// Build a list node from its items.
+    fn list(&mut self, start: Option<u64>, items: Vec<AdfNode>) -> AdfNode {
+        let is_task =
+            |node: &AdfNode| matches!(node, AdfNode::TaskItem { .. } | AdfNode::TaskList { .. });
+        if start.is_none()
+            && items.iter().any(|n| matches!(n, AdfNode::TaskItem { .. }))
+            && items.iter().all(is_task)
+        {
+            let local_id = self.local_id();
+            return AdfNode::TaskList {
+                attrs: LocalIdAttrs { local_id },
+                content: items,
+            };
+        }
+
+        // Checklist items mixed with ordinary ones become ordinary items, and
+        // lists nested under a checklist item move into the item before them.
+        let mut list_items: Vec<AdfNode> = Vec::with_capacity(items.len());
+        for node in items {
+            match node {
+                AdfNode::ListItem { .. } => list_items.push(node),
+                AdfNode::TaskItem { content, .. } => list_items.push(AdfNode::ListItem {
+                    content: vec![AdfNode::Paragraph { content }],
+                }),
+                nested => match list_items.last_mut() {
+                    Some(AdfNode::ListItem { content }) => content.push(nested),
+                    _ => list_items.push(AdfNode::ListItem {
+                        content: vec![nested],
+                    }),
+                },
+            }
+        }
+        let items = list_items;
+        match start {
+            Some(start) => AdfNode::OrderedList {
+                attrs: (start != 1).then_some(OrderedListAttrs {
+                    order: start as u32,
+                }),
+                content: items,
+            },
+            None => AdfNode::BulletList { content: items },
+        }
+    }
+
+    /// Add text with the current marks, plus `extra`.
+    fn text(&mut self, text: &str, extra: Option<AdfMark>) {
+        if text.is_empty() {
+            return;
+        }
+        // Inline code may only be combined with links.
+        let mut marks: Vec<AdfMark> = match extra {
+            Some(AdfMark::Code) => self
+                .marks
+                .iter()
+                .filter(|mark| matches!(mark, AdfMark::Link { .. }))
+                .cloned()
+                .collect(),
+            _ => self.marks.clone(),
+        };
+        marks.extend(extra);
+        self.push(AdfNode::Text {
+            text: text.to_string(),
+            marks,
+        });
+    }
+
+    /// Add a node to the innermost open container.
+    fn push(&mut self, node: AdfNode) {
+        if let Some((_, children)) = self.stack.last_mut() {
+            children.push(node);
+        }
+    }
+
+    /// A fresh local ID for a checklist node.
+    fn local_id(&mut self) -> String {
+        self.next_id += 1;
+        self.next_id.to_string()
+    }
+
+    /// The top-level blocks of the document.
+    fn finish(mut self) -> Vec<AdfNode> {
+        let children = self
+            .stack
+            .drain(..)
+            .next()
+            .map(|(_, children)| children)
+            .unwrap_or_default();
+        blocks(children)
+    }
+}
+
+/// Wrap runs of inline nodes in paragraphs, as block containers require.
+fn blocks(nodes: Vec<AdfNode>) -> Vec<AdfNode> {
+    let mut out = Vec::new();
+    let mut inline = Vec::new();
+    for node in nodes {
+        if node.is_inline() {
+            inline.push(node);
+            continue;
+        }
+        if !inline.is_empty() {
+            out.push(AdfNode::Paragraph {
+                content: merge_text(std::mem::take(&mut inline)),
+            });
+        }
+        out.push(node);
+    }
+    if !inline.is_empty() {
+        out.push(AdfNode::Paragraph {
+            content: merge_text(inline),
+        });
+    }
+    out
+}
+
+/// Join adjacent text nodes that carry the same marks.
+fn merge_text(nodes: Vec<AdfNode>) -> Vec<AdfNode> {
+    let mut out: Vec<AdfNode> = Vec::with_capacity(nodes.len());
+    for node in nodes {
+        if let (
+            Some(AdfNode::Text {
+                text: last,
+                marks: last_marks,
+            }),
+            AdfNode::Text { text, marks },
+        ) = (out.last_mut(), &node)
+        {
+            if last_marks == marks {
+                last.push_str(text);
+                continue;
+            }
+        }
+        out.push(node);
+    }
+    out
+}
+
+/// Whether inline HTML is a line break, as used inside table cells.
+fn is_line_break(html: &str) -> bool {
+    matches!(
+        html.trim().to_ascii_lowercase().as_str(),
+        "<br>" | "<br/>" | "<br />"
+    )
+}
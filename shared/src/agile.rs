@@ -0,0 +1,395 @@
+//! Jira Agile boards and sprints.
+
+use crate::errors::{JiraError, JiraResult};
+use crate::jira::{error_message, JiraClient, JiraIssue, JiraSearchOptions};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Page size used when listing boards, sprints and sprint issues.
+const AGILE_PAGE_SIZE: usize = 50;
+
+/// Largest number of issues Jira moves in one request.
+const MAX_ISSUES_PER_MOVE: usize = 50;
+
+/// A Scrum or Kanban board.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraBoard {
+    /// Board ID
+    pub id: u64,
+    /// Board name
+    pub name: String,
+    /// Board type ("scrum", "kanban" or "simple")
+    #[serde(rename = "type")]
+    pub board_type: String,
+    /// Project the board belongs to
+    pub location: Option<JiraBoardLocation>,
+}
+
+/// Project a board belongs to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraBoardLocation {
+    /// Project key
+    #[serde(rename = "projectKey")]
+    pub project_key: Option<String>,
+    /// Display name, usually "Project name (KEY)"
+    #[serde(rename = "displayName")]
+    pub display_name: Option<String>,
+}
+
+/// A sprint of a Scrum board.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraSprint {
+    /// Sprint ID
+    pub id: u64,
+    /// Sprint name
+    pub name: String,
+    /// Sprint state ("future", "active" or "closed")
+    pub state: String,
+    /// Planned or actual start
+    #[serde(rename = "startDate")]
+    pub start_date: Option<String>,
+    /// Planned end
+    #[serde(rename = "endDate")]
+    pub end_date: Option<String>,
+    /// When the sprint was closed
+    #[serde(rename = "completeDate")]
+    pub complete_date: Option<String>,
+    /// Sprint goal
+    pub goal: Option<String>,
+    /// Board the sprint was created on
+    #[serde(rename = "originBoardId")]
+    pub origin_board_id: Option<u64>,
+}
+
+/// Configuration of a board, as far as it matters for estimation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraBoardConfiguration {
+    /// How issues on the board are estimated
+    pub estimation: Option<JiraBoardEstimation>,
+}
+
+/// Estimation statistic of a board.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraBoardEstimation {
+    /// "field" when estimates come from a field, "issueCount" otherwise
+    #[serde(rename = "type")]
+    pub estimation_type: String,
+    /// Field holding the estimate (e.g., the story point field)
+    pub field: Option<JiraEstimationField>,
+}
+
+/// Field used for board estimates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraEstimationField {
+    /// Field ID (e.g., "customfield_10016")
+    #[serde(rename = "fieldId")]
+    pub field_id: String,
+    /// Field name
+    #[serde(rename = "displayName")]
+    pub display_name: Option<String>,
+}
+
+/// A page of an Agile listing.
+#[derive(Debug, Deserialize)]
+struct AgilePage<T> {
+    #[serde(rename = "isLast", default)]
+    is_last: bool,
+    #[serde(default = "Vec::new")]
+    values: Vec<T>,
+}
+
+/// A page of sprint issues.
+#[derive(Debug, Deserialize)]
+struct SprintIssuesPage {
+    #[serde(default)]
+    total: usize,
+    #[serde(default)]
+    issues: Vec<JiraIssue>,
+}
+
+impl JiraClient {
+    /// List boards, optionally only those of a project or whose name contains `name`.
+    pub fn get_boards(
+        &self,
+        project_key: Option<&str>,
+        name: Option<&str>,
+    ) -> JiraResult<Vec<JiraBoard>> {
+        let mut query = String::new();
+        if let Some(project_key) = project_key {
+            query.push_str(&format!(
+                "&projectKeyOrId={}",
+                urlencoding::encode(project_key)
+            ));
+        }
+        if let Some(name) = name {
+            query.push_str(&format!("&name={}", urlencoding::encode(name)));
+        }
+        self.get_agile_pages("/board", &query, "list boards")
+    }
+
+    /// Get a board by ID.
+    pub fn get_board(&self, board_id: u64) -> JiraResult<JiraBoard> {
+        let url = format!("{}/rest/agile/1.0/board/{board_id}", self.config.base_url);
+
+        let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+        if response.status() == 404 {
+            return Err(JiraError::BoardNotFound { id: board_id });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = error_message(response);
+            return Err(JiraError::ApiError {
+                status,
+                message: format!("Failed to get board {board_id}: HTTP {status}: {message}"),
+            });
+        }
+
+        Ok(response.json()?)
+    }
+
+    /// Get a board's estimation configuration.
+    pub fn get_board_configuration(&self, board_id: u64) -> JiraResult<JiraBoardConfiguration> {
+        let url = format!(
+            "{}/rest/agile/1.0/board/{board_id}/configuration",
+            self.config.base_url
+        );
+
+        let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+        if response.status() == 404 {
+            return Err(JiraError::BoardNotFound { id: board_id });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = error_message(response);
+            return Err(JiraError::ApiError {
+                status,
+                message: format!(
+                    "Failed to get configuration of board {board_id}: HTTP {status}: {message}"
+                ),
+            });
+        }
+
+        Ok(response.json()?)
+    }
+
+    /// List the sprints of a board, optionally only those in the given states.
+    pub fn get_sprints(&self, board_id: u64, states: &[&str]) -> JiraResult<Vec<JiraSprint>> {
+        let query = if states.is_empty() {
+            String::new()
+        } else {
+            format!("&state={}", states.join(","))
+        };
+        self.get_agile_pages(
+            &format!("/board/{board_id}/sprint"),
+            &query,
+            &format!("list sprints of board {board_id}"),
+        )
+        .map_err(|e| match e {
+            JiraError::ApiError { status: 404, .. } => JiraError::BoardNotFound { id: board_id },
+            e => e,
+        })
+    }
+
+    /// Get a sprint by ID.
+    pub fn get_sprint(&self, sprint_id: u64) -> JiraResult<JiraSprint> {
+        let url = format!("{}/rest/agile/1.0/sprint/{sprint_id}", self.config.base_url);
+
+        let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+        if response.status() == 404 {
+            return Err(JiraError::SprintNotFound { id: sprint_id });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = error_message(response);
+            return Err(JiraError::ApiError {
+                status,
+                message: format!("Failed to get sprint {sprint_id}: HTTP {status}: {message}"),
+            });
+        }
+
+        Ok(response.json()?)
+    }
+
+    /// Get the issues of a sprint, including issues completed in it.
+    pub fn get_sprint_issues(
+        &self,
+        sprint_id: u64,
+        options: &JiraSearchOptions,
+    ) -> JiraResult<Vec<JiraIssue>> {
+        let mut issues = Vec::new();
+        loop {
+            let mut url = format!(
+                "{}/rest/agile/1.0/sprint/{sprint_id}/issue?startAt={}&maxResults={AGILE_PAGE_SIZE}",
+                self.config.base_url,
+                issues.len()
+            );
+            if !options.fields.is_empty() {
+                url.push_str(&format!(
+                    "&fields={}",
+                    urlencoding::encode(&options.fields.join(","))
+                ));
+            }
+            if !options.expand.is_empty() {
+                url.push_str(&format!(
+                    "&expand={}",
+                    urlencoding::encode(&options.expand.join(","))
+                ));
+            }
+
+            let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+            if response.status() == 404 {
+                return Err(JiraError::SprintNotFound { id: sprint_id });
+            }
+
+            if !response.status().is_success() {
+                let status = response.status().as_u16();
+                let message = error_message(response);
+                return Err(JiraError::ApiError {
+                    status,
+                    message: format!(
+                        "Failed to get issues of sprint {sprint_id}: HTTP {status}: {message}"
+                    ),
+                });
+            }
+
+            let page: SprintIssuesPage = response.json()?;
+            let received = page.issues.len();
+            issues.extend(page.issues);
+            if received == 0
+                || issues.len() >= page.total
+                || options.limit.is_some_and(|limit| issues.len() >= limit)
+            {
+                break;
+            }
+        }
+        if let Some(limit) = options.limit {
+            issues.truncate(limit);
+        }
+        Ok(issues)
+    }
+
+    /// Move issues into a sprint, or to the backlog when `sprint_id` is `None`.
+    pub fn move_issues_to_sprint(&self, sprint_id: Option<u64>, keys: &[&str]) -> JiraResult<()> {
+        let url = match sprint_id {
+            Some(id) => format!("{}/rest/agile/1.0/sprint/{id}/issue", self.config.base_url),
+            None => format!("{}/rest/agile/1.0/backlog/issue", self.config.base_url),
+        };
+        let target =
+            sprint_id.map_or_else(|| "the backlog".to_string(), |id| format!("sprint {id}"));
+
+        for chunk in keys.chunks(MAX_ISSUES_PER_MOVE) {
+            let response = self
+                .client
+                .post(&url)
+                .headers(self.headers.clone())
+                .json(&json!({ "issues": chunk }))
+                .send()?;
+
+            if response.status() == 404 {
+                if let Some(id) = sprint_id {
+                    return Err(JiraError::SprintNotFound { id });
+                }
+            }
+
+            if !response.status().is_success() {
+                let status = response.status().as_u16();
+                let message = error_message(response);
+                return Err(JiraError::ApiError {
+                    status,
+                    message: format!("Failed to move issues to {target}: HTTP {status}: {message}"),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Start a future sprint with the given start and end dates (ISO 8601).
+    pub fn start_sprint(
+        &self,
+        sprint_id: u64,
+        start_date: &str,
+        end_date: &str,
+    ) -> JiraResult<JiraSprint> {
+        self.update_sprint(
+            sprint_id,
+            &json!({ "state": "active", "startDate": start_date, "endDate": end_date }),
+            "start",
+        )
+    }
+
+    /// Close an active sprint.
+    pub fn close_sprint(&self, sprint_id: u64) -> JiraResult<JiraSprint> {
+        self.update_sprint(sprint_id, &json!({ "state": "closed" }), "close")
+    }
+
+    /// Partially update a sprint.
+    fn update_sprint(&self, sprint_id: u64, body: &Value, action: &str) -> JiraResult<JiraSprint> {
+        let url = format!("{}/rest/agile/1.0/sprint/{sprint_id}", self.config.base_url);
+
+        let response = self
+            .client
+            .post(&url)
+            .headers(self.headers.clone())
+            .json(body)
+            .send()?;
+
+        if response.status() == 404 {
+            return Err(JiraError::SprintNotFound { id: sprint_id });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = error_message(response);
+            return Err(JiraError::ApiError {
+                status,
+                message: format!("Failed to {action} sprint {sprint_id}: HTTP {status}: {message}"),
+            });
+        }
+
+        Ok(response.json()?)
+    }
+
+    /// Fetch every page of an Agile listing under `/rest/agile/1.0`.
+    fn get_agile_pages<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &str,
+        action: &str,
+    ) -> JiraResult<Vec<T>> {
+        let mut values = Vec::new();
+        loop {
+            let url = format!(
+                "{}/rest/agile/1.0{path}?startAt={}&maxResults={AGILE_PAGE_SIZE}{query}",
+                self.config.base_url,
+                values.len()
+            );
+
+            let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+            if !response.status().is_success() {
+                let status = response.status().as_u16();
+                let message = error_message(response);
+                return Err(JiraError::ApiError {
+                    status,
+                    message: format!("Failed to {action}: HTTP {status}: {message}"),
+                });
+            }
+
+            let page: AgilePage<T> = response.json()?;
+            let received = page.values.len();
+            values.extend(page.values);
+            if page.is_last || received == 0 {
+                break;
+            }
+        }
+        Ok(values)
+    }
+}
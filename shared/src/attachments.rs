@@ -0,0 +1,199 @@
+//! Page attachments: listing, uploading and downloading files.
+
+use crate::confluence::{ConfluenceClient, SearchLinks};
+use crate::errors::{ConfluenceError, Result};
+use reqwest::blocking::multipart::Form;
+use reqwest::header::{HeaderValue, CONTENT_TYPE};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::path::Path;
+
+/// An attachment on a Confluence page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attachment {
+    /// Attachment ID
+    pub id: String,
+    /// File name
+    pub title: String,
+    /// Attachment details such as media type and size
+    pub extensions: Option<AttachmentExtensions>,
+    /// Attachment version
+    pub version: Option<crate::content::PageVersion>,
+    /// Attachment links
+    #[serde(rename = "_links")]
+    pub links: Option<AttachmentLinks>,
+}
+
+/// Extra details about an attachment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachmentExtensions {
+    /// MIME type (e.g. "image/png")
+    pub media_type: Option<String>,
+    /// Size in bytes
+    pub file_size: Option<u64>,
+    /// Upload comment
+    pub comment: Option<String>,
+}
+
+/// Links associated with an attachment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentLinks {
+    /// Download link relative to the wiki base URL
+    pub download: Option<String>,
+    /// Web UI link
+    pub webui: Option<String>,
+}
+
+/// Response from the attachment listing endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AttachmentsResponse {
+    results: Vec<Attachment>,
+    #[serde(rename = "_links")]
+    links: Option<SearchLinks>,
+}
+
+impl Attachment {
+    /// MIME type of the attachment, if known.
+    pub fn media_type(&self) -> Option<&str> {
+        self.extensions.as_ref()?.media_type.as_deref()
+    }
+
+    /// Size of the attachment in bytes, if known.
+    pub fn file_size(&self) -> Option<u64> {
+        self.extensions.as_ref()?.file_size
+    }
+}
+
+impl ConfluenceClient {
+    /// List all attachments on a page.
+    pub fn get_attachments(&self, page_id: &str) -> Result<Vec<Attachment>> {
+        let mut url = format!(
+            "{}/wiki/rest/api/content/{}/child/attachment?expand=version&limit=100",
+            self.config.base_url, page_id
+        );
+        let mut attachments = Vec::new();
+
+        loop {
+            let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+            if response.status() == 404 {
+                return Err(ConfluenceError::PageNotFound {
+                    page_id: page_id.to_string(),
+                });
+            }
+
+            if !response.status().is_success() {
+                let status = response.status().as_u16();
+                let error_text = response
+                    .text()
+                    .unwrap_or_else(|_| "Unknown error".to_string());
+                return Err(ConfluenceError::ApiError {
+                    status,
+                    message: format!(
+                        "Failed to list attachments on page {page_id}: HTTP {status}: {error_text}"
+                    ),
+                });
+            }
+
+            let page: AttachmentsResponse = response.json()?;
+            attachments.extend(page.results);
+
+            match page.links.and_then(|l| l.next) {
+                Some(next) => url = format!("{}/wiki{}", self.config.base_url, next),
+                None => break,
+            }
+        }
+
+        Ok(attachments)
+    }
+
+    /// Upload a file as an attachment to a page.
+    ///
+    /// When `new_version_if_exists` is true an attachment with the same file
+    /// name gets a new version; otherwise uploading a duplicate name fails.
+    pub fn upload_attachment(
+        &self,
+        page_id: &str,
+        path: &Path,
+        new_version_if_exists: bool,
+    ) -> Result<Attachment> {
+        let url = format!(
+            "{}/wiki/rest/api/content/{}/child/attachment",
+            self.config.base_url, page_id
+        );
+
+        // The multipart body sets its own content type.
+        let mut headers = self.headers.clone();
+        headers.remove(CONTENT_TYPE);
+        headers.insert("X-Atlassian-Token", HeaderValue::from_static("no-check"));
+
+        let form = Form::new().file("file", path)?;
+        let request = if new_version_if_exists {
+            self.client.put(&url)
+        } else {
+            self.client.post(&url)
+        };
+        let response = request.headers(headers).multipart(form).send()?;
+
+        if response.status() == 404 {
+            return Err(ConfluenceError::PageNotFound {
+                page_id: page_id.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!(
+                    "Failed to upload {} to page {page_id}: HTTP {status}: {error_text}",
+                    path.display()
+                ),
+            });
+        }
+
+        let uploaded: AttachmentsResponse = response.json()?;
+        uploaded
+            .results
+            .into_iter()
+            .next()
+            .ok_or_else(|| ConfluenceError::InvalidResponse {
+                message: format!("Upload of {} returned no attachment", path.display()),
+            })
+    }
+
+    /// Stream an attachment's content into a file, returning the bytes written.
+    pub fn download_attachment(&self, attachment: &Attachment, dest: &Path) -> Result<u64> {
+        let download = attachment
+            .links
+            .as_ref()
+            .and_then(|l| l.download.as_deref())
+            .ok_or_else(|| ConfluenceError::InvalidResponse {
+                message: format!("Attachment {} has no download link", attachment.id),
+            })?;
+        let url = format!("{}/wiki{}", self.config.base_url, download);
+
+        let mut response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!(
+                    "Failed to download attachment '{}': HTTP {status}: {error_text}",
+                    attachment.title
+                ),
+            });
+        }
+
+        let mut file = File::create(dest)?;
+        Ok(response.copy_to(&mut file)?)
+    }
+}
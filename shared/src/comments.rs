@@ -0,0 +1,295 @@
+//! Page comments: footer and inline comments, and inline comment resolution.
+
+use crate::confluence::{ConfluenceClient, SearchLinks};
+use crate::content::{PageBody, PageVersion, StorageBody};
+use crate::errors::{ConfluenceError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Where a comment is attached on a page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentLocation {
+    /// Comment at the bottom of the page
+    Footer,
+    /// Comment anchored to a text selection
+    Inline,
+}
+
+/// A comment on a Confluence page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comment {
+    /// Comment ID
+    pub id: String,
+    /// Comment title (usually "Re: <page title>")
+    pub title: Option<String>,
+    /// Comment status (usually "current")
+    pub status: String,
+    /// Comment body (requires `body.storage` expansion)
+    pub body: Option<PageBody>,
+    /// Comment version, holding the author and date
+    pub version: Option<PageVersion>,
+    /// Location and resolution details
+    pub extensions: Option<CommentExtensions>,
+}
+
+/// Extra details about a comment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentExtensions {
+    /// "footer" or "inline"
+    pub location: Option<String>,
+    /// Resolution state of an inline comment
+    pub resolution: Option<CommentResolution>,
+    /// Text the inline comment is anchored to
+    pub inline_properties: Option<InlineProperties>,
+}
+
+/// Resolution state of an inline comment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentResolution {
+    /// "open", "reopened", "resolved" or "dangling"
+    pub status: String,
+    /// When the resolution last changed
+    pub last_modified_date: Option<String>,
+}
+
+/// Anchor information for an inline comment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InlineProperties {
+    /// Selected text the comment refers to
+    pub original_selection: Option<String>,
+}
+
+/// Response from the comment listing endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CommentsResponse {
+    results: Vec<Comment>,
+    #[serde(rename = "_links")]
+    links: Option<SearchLinks>,
+}
+
+/// Inline comment as returned by the v2 API, used to read the current version.
+#[derive(Debug, Clone, Deserialize)]
+struct InlineCommentV2 {
+    version: InlineCommentV2Version,
+    body: Option<InlineCommentV2Body>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct InlineCommentV2Version {
+    number: i32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct InlineCommentV2Body {
+    storage: Option<StorageBody>,
+}
+
+impl CommentLocation {
+    /// Name used by the REST API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommentLocation::Footer => "footer",
+            CommentLocation::Inline => "inline",
+        }
+    }
+}
+
+impl Comment {
+    /// Display name of the comment's author.
+    pub fn author(&self) -> &str {
+        self.version
+            .as_ref()
+            .and_then(|v| v.by.as_ref())
+            .map_or("unknown", |by| by.name())
+    }
+
+    /// Timestamp of the comment's latest version.
+    pub fn date(&self) -> Option<&str> {
+        self.version.as_ref()?.when.as_deref()
+    }
+
+    /// Location of the comment, defaulting to footer.
+    pub fn location(&self) -> CommentLocation {
+        match self.extensions.as_ref().and_then(|e| e.location.as_deref()) {
+            Some("inline") => CommentLocation::Inline,
+            _ => CommentLocation::Footer,
+        }
+    }
+
+    /// Resolution status of an inline comment ("open", "resolved", ...).
+    pub fn resolution(&self) -> Option<&str> {
+        self.extensions
+            .as_ref()
+            .and_then(|e| e.resolution.as_ref())
+            .map(|r| r.status.as_str())
+    }
+
+    /// Whether the comment is an inline comment that has been resolved.
+    pub fn is_resolved(&self) -> bool {
+        self.resolution() == Some("resolved")
+    }
+
+    /// Storage-format body of the comment.
+    pub fn storage_body(&self) -> &str {
+        self.body
+            .as_ref()
+            .and_then(|b| b.storage.as_ref())
+            .map_or("", |s| s.value.as_str())
+    }
+}
+
+impl ConfluenceClient {
+    /// List all comments (including replies) on a page at the given locations.
+    pub fn get_page_comments(
+        &self,
+        page_id: &str,
+        locations: &[CommentLocation],
+    ) -> Result<Vec<Comment>> {
+        let location_params: String = locations
+            .iter()
+            .map(|l| format!("&location={}", l.as_str()))
+            .collect();
+        let mut url = format!(
+            "{}/wiki/rest/api/content/{}/child/comment?expand=body.storage,version,extensions.inlineProperties,extensions.resolution&depth=all&limit=100{}",
+            self.config.base_url, page_id, location_params
+        );
+        let mut comments = Vec::new();
+
+        loop {
+            let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+            if response.status() == 404 {
+                return Err(ConfluenceError::PageNotFound {
+                    page_id: page_id.to_string(),
+                });
+            }
+
+            if !response.status().is_success() {
+                let status = response.status().as_u16();
+                let error_text = response
+                    .text()
+                    .unwrap_or_else(|_| "Unknown error".to_string());
+                return Err(ConfluenceError::ApiError {
+                    status,
+                    message: format!(
+                        "Failed to list comments on page {page_id}: HTTP {status}: {error_text}"
+                    ),
+                });
+            }
+
+            let page: CommentsResponse = response.json()?;
+            comments.extend(page.results);
+
+            match page.links.and_then(|l| l.next) {
+                Some(next) => url = format!("{}/wiki{}", self.config.base_url, next),
+                None => break,
+            }
+        }
+
+        Ok(comments)
+    }
+
+    /// Add a footer comment to a page.
+    pub fn add_page_comment(&self, page_id: &str, body: &StorageBody) -> Result<Comment> {
+        let url = format!("{}/wiki/rest/api/content", self.config.base_url);
+
+        let request_body = serde_json::json!({
+            "type": "comment",
+            "container": { "id": page_id, "type": "page" },
+            "body": { "storage": body },
+        });
+
+        let response = self
+            .client
+            .post(&url)
+            .headers(self.headers.clone())
+            .json(&request_body)
+            .send()?;
+
+        if response.status() == 404 {
+            return Err(ConfluenceError::PageNotFound {
+                page_id: page_id.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!(
+                    "Failed to add comment to page {page_id}: HTTP {status}: {error_text}"
+                ),
+            });
+        }
+
+        Ok(response.json()?)
+    }
+
+    /// Resolve (`true`) or reopen (`false`) an inline comment.
+    pub fn set_inline_comment_resolved(&self, comment_id: &str, resolved: bool) -> Result<()> {
+        let url = format!(
+            "{}/wiki/api/v2/inline-comments/{}",
+            self.config.base_url, comment_id
+        );
+
+        // The update endpoint requires the current body and the next version.
+        let response = self
+            .client
+            .get(format!("{url}?body-format=storage"))
+            .headers(self.headers.clone())
+            .send()?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!(
+                    "Failed to get inline comment {comment_id}: HTTP {status}: {error_text}"
+                ),
+            });
+        }
+
+        let current: InlineCommentV2 = response.json()?;
+        let body = current
+            .body
+            .and_then(|b| b.storage)
+            .unwrap_or_else(|| StorageBody::storage(""));
+
+        let request_body = serde_json::json!({
+            "version": { "number": current.version.number + 1 },
+            "body": body,
+            "resolved": resolved,
+        });
+
+        let response = self
+            .client
+            .put(&url)
+            .headers(self.headers.clone())
+            .json(&request_body)
+            .send()?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            let action = if resolved { "resolve" } else { "reopen" };
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!(
+                    "Failed to {action} inline comment {comment_id}: HTTP {status}: {error_text}"
+                ),
+            });
+        }
+
+        Ok(())
+    }
+}
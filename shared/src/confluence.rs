@@ -1,7 +1,8 @@
+use crate::content::{PageBody, PageHistory, PageVersion};
 use crate::errors::{ConfluenceError, Result};
 use base64::Engine;
 use reqwest::blocking::Client;
-use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::header::{HeaderMap, HeaderValue, InvalidHeaderValue, AUTHORIZATION, CONTENT_TYPE};
 use serde::{Deserialize, Serialize};
 use url::Url;
 
@@ -35,6 +36,14 @@ pub struct ConfluencePage {
     pub ancestors: Option<Vec<ConfluencePage>>,
     /// Page labels
     pub metadata: Option<PageMetadata>,
+    /// Page version (requires `version` expansion)
+    pub version: Option<PageVersion>,
+    /// Page body (requires `body.storage` expansion)
+    pub body: Option<PageBody>,
+    /// Space containing the page (requires `space` expansion)
+    pub space: Option<ConfluenceSpace>,
+    /// Creation details (requires `history` expansion)
+    pub history: Option<PageHistory>,
 }
 
 /// Links associated with a Confluence page.
@@ -85,6 +94,16 @@ pub struct CqlSearchResponse {
     pub limit: i32,
     /// Total number of results
     pub size: i32,
+    /// Pagination links
+    #[serde(rename = "_links")]
+    pub links: Option<SearchLinks>,
+}
+
+/// Pagination links returned by search endpoints.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchLinks {
+    /// Relative link to the next page of results, if any
+    pub next: Option<String>,
 }
 
 /// Request body for adding labels to a page.
@@ -206,9 +225,25 @@ pub struct SpacesResponse {
 
 /// Client for interacting with the Confluence REST API.
 pub struct ConfluenceClient {
-    client: Client,
-    config: ConfluenceConfig,
-    headers: HeaderMap,
+    pub(crate) client: Client,
+    pub(crate) config: ConfluenceConfig,
+    pub(crate) headers: HeaderMap,
+}
+
+/// Build the basic auth and content type headers shared by Atlassian clients.
+pub(crate) fn auth_headers(
+    config: &ConfluenceConfig,
+) -> std::result::Result<HeaderMap, InvalidHeaderValue> {
+    let mut headers = HeaderMap::new();
+    // Use basic auth with username and API token
+    let auth_string = format!("{}:{}", config.username, config.api_token);
+    let auth_header = format!(
+        "Basic {}",
+        base64::engine::general_purpose::STANDARD.encode(&auth_string)
+    );
+    headers.insert(AUTHORIZATION, HeaderValue::from_str(&auth_header)?);
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    Ok(headers)
 }
 
 impl ConfluenceClient {
@@ -221,21 +256,9 @@ impl ConfluenceClient {
         // Set up HTTP client
         let client = Client::new();
         // Set up authentication headers
-        let mut headers = HeaderMap::new();
-        // Use basic auth with username and API token
-        let auth_string = format!("{}:{}", config.username, config.api_token);
-        let auth_header = format!(
-            "Basic {}",
-            base64::engine::general_purpose::STANDARD.encode(&auth_string)
-        );
-        headers.insert(
-            AUTHORIZATION,
-            HeaderValue::from_str(&auth_header).map_err(|_| ConfluenceError::Authentication {
-                message: "Failed to create authorization header".to_string(),
-            })?,
-        );
-
-        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        let headers = auth_headers(&config).map_err(|_| ConfluenceError::Authentication {
+            message: "Failed to create authorization header".to_string(),
+        })?;
         Ok(ConfluenceClient {
             client,
             config,
@@ -268,6 +291,51 @@ impl ConfluenceClient {
         Ok(search_response.results)
     }
 
+    /// Execute a CQL query and return every matching page, following pagination links.
+    pub fn query_all_pages_by_cql(&self, cql: &str) -> Result<Vec<ConfluencePage>> {
+        self.query_all_pages_by_cql_with_expand(cql, "metadata.labels,ancestors,space")
+    }
+
+    /// Execute a CQL query with custom expansions and return every matching page.
+    pub fn query_all_pages_by_cql_with_expand(
+        &self,
+        cql: &str,
+        expand: &str,
+    ) -> Result<Vec<ConfluencePage>> {
+        let mut url = format!(
+            "{}/wiki/rest/api/content/search?cql={}&expand={}&limit=100",
+            self.config.base_url,
+            urlencoding::encode(cql),
+            expand
+        );
+        let mut pages = Vec::new();
+
+        loop {
+            let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+            if !response.status().is_success() {
+                let status = response.status().as_u16();
+                let error_text = response
+                    .text()
+                    .unwrap_or_else(|_| "Unknown error".to_string());
+                return Err(ConfluenceError::CqlQuery {
+                    query: cql.to_string(),
+                    message: format!("HTTP {status}: {error_text}"),
+                });
+            }
+
+            let search_response: CqlSearchResponse = response.json()?;
+            pages.extend(search_response.results);
+
+            match search_response.links.and_then(|l| l.next) {
+                Some(next) => url = format!("{}/wiki{}", self.config.base_url, next),
+                None => break,
+            }
+        }
+
+        Ok(pages)
+    }
+
     /// Get labels for a specific page.
     pub fn get_page_labels(&self, page_id: &str) -> Result<Vec<String>> {
         let url = format!(
@@ -382,14 +450,17 @@ impl ConfluenceClient {
         Ok(())
     }
 
-    /// Update a label on a page (remove old, add new).
+    /// Update a label on a page (add new, then remove old).
+    ///
+    /// The new label is added first so a failure part-way never leaves the
+    /// page without either label.
     pub fn update_page_label(&self, page_id: &str, old_label: &str, new_label: &str) -> Result<()> {
-        // Remove the old label
-        self.remove_page_labels(page_id, &[old_label])?;
-
         // Add the new label
         self.add_page_labels(page_id, &[new_label])?;
 
+        // Remove the old label
+        self.remove_page_labels(page_id, &[old_label])?;
+
         Ok(())
     }
 
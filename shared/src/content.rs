@@ -0,0 +1,680 @@
+//! Page content operations: reading, creating and updating pages, content
+//! properties and archiving.
+
+use crate::confluence::{ConfluenceClient, ConfluencePage};
+use crate::errors::{ConfluenceError, Result};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// How often a long-running task is polled.
+const LONG_TASK_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long to wait for a long-running task before giving up.
+const LONG_TASK_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Version information for a Confluence page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageVersion {
+    /// Version number (starts at 1)
+    pub number: i32,
+    /// Optional version message
+    pub message: Option<String>,
+    /// Timestamp of the version
+    pub when: Option<String>,
+    /// User who created the version
+    pub by: Option<ContentUser>,
+    /// Whether the version was a minor edit
+    #[serde(rename = "minorEdit")]
+    pub minor_edit: Option<bool>,
+}
+
+/// A user referenced by content (author, modifier, etc.).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentUser {
+    /// Atlassian account ID
+    pub account_id: Option<String>,
+    /// Display name
+    pub display_name: Option<String>,
+}
+
+impl ContentUser {
+    /// Best available name for display.
+    pub fn name(&self) -> &str {
+        self.display_name
+            .as_deref()
+            .or(self.account_id.as_deref())
+            .unwrap_or("unknown")
+    }
+}
+
+/// Creation details of a Confluence page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageHistory {
+    /// User who created the page
+    pub created_by: Option<ContentUser>,
+    /// Timestamp of the page's creation
+    pub created_date: Option<String>,
+}
+
+/// Body of a Confluence page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageBody {
+    /// Storage format representation
+    pub storage: Option<StorageBody>,
+}
+
+/// A page body in a given representation (usually "storage").
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageBody {
+    /// Body markup
+    pub value: String,
+    /// Representation name
+    pub representation: String,
+}
+
+/// A content property attached to a page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentProperty {
+    /// Property key
+    pub key: String,
+    /// Arbitrary JSON value
+    pub value: serde_json::Value,
+    /// Property version
+    pub version: Option<PropertyVersion>,
+}
+
+/// Version of a content property.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PropertyVersion {
+    /// Version number
+    pub number: i32,
+}
+
+/// Response from the content listing endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ContentListResponse {
+    results: Vec<ConfluencePage>,
+}
+
+/// Options controlling what is carried over when copying pages.
+#[derive(Debug, Clone)]
+pub struct CopyPageOptions {
+    /// Copy attachments
+    pub copy_attachments: bool,
+    /// Copy labels
+    pub copy_labels: bool,
+    /// Copy page restrictions
+    pub copy_permissions: bool,
+    /// Prefix added to every copied title (required when copying within a space)
+    pub title_prefix: Option<String>,
+}
+
+impl Default for CopyPageOptions {
+    fn default() -> Self {
+        CopyPageOptions {
+            copy_attachments: true,
+            copy_labels: true,
+            copy_permissions: false,
+            title_prefix: None,
+        }
+    }
+}
+
+/// Status of an asynchronous (long-running) Confluence task.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LongTaskStatus {
+    /// Task ID
+    pub id: String,
+    /// Completion percentage
+    pub percentage_complete: Option<i32>,
+    /// Whether the task succeeded
+    pub successful: Option<bool>,
+    /// Whether the task has finished
+    pub finished: Option<bool>,
+    /// Messages reported by the task
+    #[serde(default)]
+    pub messages: Vec<serde_json::Value>,
+}
+
+impl StorageBody {
+    /// Build a storage-format body from markup.
+    pub fn storage(value: impl Into<String>) -> Self {
+        StorageBody {
+            value: value.into(),
+            representation: "storage".to_string(),
+        }
+    }
+}
+
+impl ConfluenceClient {
+    /// Fetch a single page with the given expansions (e.g. "version,body.storage").
+    pub fn get_page(&self, page_id: &str, expand: &str) -> Result<ConfluencePage> {
+        let url = format!(
+            "{}/wiki/rest/api/content/{}?expand={}",
+            self.config.base_url,
+            page_id,
+            urlencoding::encode(expand)
+        );
+
+        let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+        if response.status() == 404 {
+            return Err(ConfluenceError::PageNotFound {
+                page_id: page_id.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: error_text,
+            });
+        }
+
+        Ok(response.json()?)
+    }
+
+    /// Find a page by its exact title within a space.
+    pub fn find_page_by_title(
+        &self,
+        space_key: &str,
+        title: &str,
+    ) -> Result<Option<ConfluencePage>> {
+        let url = format!(
+            "{}/wiki/rest/api/content?type=page&spaceKey={}&title={}&expand=version,metadata.labels,ancestors",
+            self.config.base_url,
+            urlencoding::encode(space_key),
+            urlencoding::encode(title)
+        );
+
+        let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!("Failed to look up page '{title}': HTTP {status}: {error_text}"),
+            });
+        }
+
+        let list: ContentListResponse = response.json()?;
+        Ok(list.results.into_iter().next())
+    }
+
+    /// Create a page in a space, optionally under a parent page.
+    pub fn create_page(
+        &self,
+        space_key: &str,
+        parent_id: Option<&str>,
+        title: &str,
+        body: &StorageBody,
+    ) -> Result<ConfluencePage> {
+        let url = format!("{}/wiki/rest/api/content", self.config.base_url);
+
+        let mut request_body = serde_json::json!({
+            "type": "page",
+            "title": title,
+            "space": { "key": space_key },
+            "body": { "storage": body },
+        });
+        if let Some(parent_id) = parent_id {
+            request_body["ancestors"] = serde_json::json!([{ "id": parent_id }]);
+        }
+
+        let response = self
+            .client
+            .post(&url)
+            .headers(self.headers.clone())
+            .json(&request_body)
+            .send()?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!("Failed to create page '{title}': HTTP {status}: {error_text}"),
+            });
+        }
+
+        Ok(response.json()?)
+    }
+
+    /// Update a page's title and body, bumping it to `version`.
+    ///
+    /// When `parent_id` is given the page is also re-parented.
+    pub fn update_page(
+        &self,
+        page_id: &str,
+        title: &str,
+        body: &StorageBody,
+        version: i32,
+        parent_id: Option<&str>,
+    ) -> Result<ConfluencePage> {
+        let url = format!("{}/wiki/rest/api/content/{}", self.config.base_url, page_id);
+
+        let mut request_body = serde_json::json!({
+            "type": "page",
+            "title": title,
+            "version": { "number": version },
+            "body": { "storage": body },
+        });
+        if let Some(parent_id) = parent_id {
+            request_body["ancestors"] = serde_json::json!([{ "id": parent_id }]);
+        }
+
+        let response = self
+            .client
+            .put(&url)
+            .headers(self.headers.clone())
+            .json(&request_body)
+            .send()?;
+
+        if response.status() == 404 {
+            return Err(ConfluenceError::PageNotFound {
+                page_id: page_id.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!("Failed to update page {page_id}: HTTP {status}: {error_text}"),
+            });
+        }
+
+        Ok(response.json()?)
+    }
+
+    /// Get a content property from a page, if it exists.
+    pub fn get_content_property(
+        &self,
+        page_id: &str,
+        key: &str,
+    ) -> Result<Option<ContentProperty>> {
+        let url = format!(
+            "{}/wiki/rest/api/content/{}/property/{}",
+            self.config.base_url,
+            page_id,
+            urlencoding::encode(key)
+        );
+
+        let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+        if response.status() == 404 {
+            return Ok(None);
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!(
+                    "Failed to get property '{key}' on page {page_id}: HTTP {status}: {error_text}"
+                ),
+            });
+        }
+
+        Ok(Some(response.json()?))
+    }
+
+    /// Create or update a content property on a page.
+    pub fn set_content_property(
+        &self,
+        page_id: &str,
+        key: &str,
+        value: serde_json::Value,
+    ) -> Result<()> {
+        let existing = self.get_content_property(page_id, key)?;
+
+        let response = match existing {
+            Some(property) => {
+                let url = format!(
+                    "{}/wiki/rest/api/content/{}/property/{}",
+                    self.config.base_url,
+                    page_id,
+                    urlencoding::encode(key)
+                );
+                let next_version = property.version.map(|v| v.number).unwrap_or(0) + 1;
+                self.client
+                    .put(&url)
+                    .headers(self.headers.clone())
+                    .json(&serde_json::json!({
+                        "key": key,
+                        "value": value,
+                        "version": { "number": next_version },
+                    }))
+                    .send()?
+            }
+            None => {
+                let url = format!(
+                    "{}/wiki/rest/api/content/{}/property",
+                    self.config.base_url, page_id
+                );
+                self.client
+                    .post(&url)
+                    .headers(self.headers.clone())
+                    .json(&serde_json::json!({ "key": key, "value": value }))
+                    .send()?
+            }
+        };
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!(
+                    "Failed to set property '{key}' on page {page_id}: HTTP {status}: {error_text}"
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Archive the given pages.
+    pub fn archive_pages(&self, page_ids: &[&str]) -> Result<()> {
+        let url = format!("{}/wiki/rest/api/content/archive", self.config.base_url);
+
+        let request_body = serde_json::json!({
+            "pages": page_ids
+                .iter()
+                .map(|id| serde_json::json!({ "id": id }))
+                .collect::<Vec<_>>(),
+        });
+
+        let response = self
+            .client
+            .post(&url)
+            .headers(self.headers.clone())
+            .json(&request_body)
+            .send()?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!("Failed to archive pages: HTTP {status}: {error_text}"),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Get all descendant pages of a page.
+    pub fn get_descendant_pages(&self, page_id: &str) -> Result<Vec<ConfluencePage>> {
+        self.query_all_pages_by_cql(&format!("ancestor = {page_id} and type = page"))
+    }
+
+    /// Get the ID of a space's home page.
+    pub fn get_space_homepage_id(&self, space_key: &str) -> Result<String> {
+        let url = format!(
+            "{}/wiki/rest/api/space/{}?expand=homepage",
+            self.config.base_url,
+            urlencoding::encode(space_key)
+        );
+
+        let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+        if response.status() == 404 {
+            return Err(ConfluenceError::SpaceNotFound {
+                space_key: space_key.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!("Failed to get space {space_key}: HTTP {status}: {error_text}"),
+            });
+        }
+
+        let space: serde_json::Value = response.json()?;
+        space["homepage"]["id"]
+            .as_str()
+            .map(|id| id.to_string())
+            .ok_or_else(|| ConfluenceError::InvalidResponse {
+                message: format!("Space {space_key} has no home page"),
+            })
+    }
+
+    /// Move a page, together with all of its descendants, under a new parent.
+    ///
+    /// The target may live in another space, which moves the subtree there.
+    pub fn move_page(&self, page_id: &str, target_parent_id: &str) -> Result<()> {
+        let url = format!(
+            "{}/wiki/rest/api/content/{}/move/append/{}",
+            self.config.base_url, page_id, target_parent_id
+        );
+
+        let response = self.client.put(&url).headers(self.headers.clone()).send()?;
+
+        if response.status() == 404 {
+            return Err(ConfluenceError::PageNotFound {
+                page_id: page_id.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!(
+                    "Failed to move page {page_id} under {target_parent_id}: HTTP {status}: {error_text}"
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Copy a single page under a new parent.
+    pub fn copy_page(
+        &self,
+        page_id: &str,
+        target_parent_id: &str,
+        options: &CopyPageOptions,
+    ) -> Result<ConfluencePage> {
+        let url = format!(
+            "{}/wiki/rest/api/content/{}/copy",
+            self.config.base_url, page_id
+        );
+
+        let mut request_body = serde_json::json!({
+            "copyAttachments": options.copy_attachments,
+            "copyLabels": options.copy_labels,
+            "copyPermissions": options.copy_permissions,
+            "copyProperties": true,
+            "copyCustomContents": true,
+            "destination": { "type": "parent_page", "value": target_parent_id },
+        });
+        if let Some(prefix) = &options.title_prefix {
+            let original = self.get_page(page_id, "")?;
+            request_body["pageTitle"] = format!("{prefix}{}", original.title).into();
+        }
+
+        let response = self
+            .client
+            .post(&url)
+            .headers(self.headers.clone())
+            .json(&request_body)
+            .send()?;
+
+        if response.status() == 404 {
+            return Err(ConfluenceError::PageNotFound {
+                page_id: page_id.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!("Failed to copy page {page_id}: HTTP {status}: {error_text}"),
+            });
+        }
+
+        Ok(response.json()?)
+    }
+
+    /// Start copying a page and all of its descendants under a new parent.
+    ///
+    /// Returns the ID of the long-running task performing the copy.
+    pub fn copy_page_hierarchy(
+        &self,
+        page_id: &str,
+        target_parent_id: &str,
+        options: &CopyPageOptions,
+    ) -> Result<String> {
+        let url = format!(
+            "{}/wiki/rest/api/content/{}/pagehierarchy/copy",
+            self.config.base_url, page_id
+        );
+
+        let mut request_body = serde_json::json!({
+            "copyAttachments": options.copy_attachments,
+            "copyLabels": options.copy_labels,
+            "copyPermissions": options.copy_permissions,
+            "copyProperties": true,
+            "copyCustomContents": true,
+            "destinationPageId": target_parent_id,
+        });
+        if let Some(prefix) = &options.title_prefix {
+            request_body["titleOptions"] = serde_json::json!({ "prefix": prefix });
+        }
+
+        let response = self
+            .client
+            .post(&url)
+            .headers(self.headers.clone())
+            .json(&request_body)
+            .send()?;
+
+        if response.status() == 404 {
+            return Err(ConfluenceError::PageNotFound {
+                page_id: page_id.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!(
+                    "Failed to copy page hierarchy {page_id}: HTTP {status}: {error_text}"
+                ),
+            });
+        }
+
+        let task: serde_json::Value = response.json()?;
+        task["id"].as_str().map(|id| id.to_string()).ok_or_else(|| {
+            ConfluenceError::InvalidResponse {
+                message: "Copy request returned no task ID".to_string(),
+            }
+        })
+    }
+
+    /// Get the status of a long-running task.
+    pub fn get_long_task(&self, task_id: &str) -> Result<LongTaskStatus> {
+        let url = format!(
+            "{}/wiki/rest/api/longtask/{}",
+            self.config.base_url, task_id
+        );
+
+        let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!("Failed to get task {task_id}: HTTP {status}: {error_text}"),
+            });
+        }
+
+        Ok(response.json()?)
+    }
+
+    /// Poll a long-running task until it finishes, failing if it was
+    /// unsuccessful or is still running after `LONG_TASK_TIMEOUT`.
+    pub fn wait_for_long_task(&self, task_id: &str) -> Result<LongTaskStatus> {
+        let started = Instant::now();
+        loop {
+            let task = self.get_long_task(task_id)?;
+            let finished = match (task.finished, task.percentage_complete) {
+                (Some(finished), _) => finished,
+                (None, Some(percentage)) => percentage >= 100,
+                (None, None) => {
+                    return Err(ConfluenceError::InvalidResponse {
+                        message: format!("Task {task_id} reported neither progress nor completion"),
+                    })
+                }
+            };
+            if finished {
+                if task.successful == Some(false) {
+                    return Err(ConfluenceError::TaskFailed {
+                        task_id: task_id.to_string(),
+                        messages: task.messages.iter().map(task_message).collect(),
+                    });
+                }
+                return Ok(task);
+            }
+            if started.elapsed() >= LONG_TASK_TIMEOUT {
+                return Err(ConfluenceError::TaskTimedOut {
+                    task_id: task_id.to_string(),
+                    seconds: LONG_TASK_TIMEOUT.as_secs(),
+                });
+            }
+            std::thread::sleep(LONG_TASK_POLL_INTERVAL);
+        }
+    }
+}
+
+/// Readable text of a long-running task message.
+fn task_message(message: &serde_json::Value) -> String {
+    message
+        .get("translation")
+        .or_else(|| message.get("key"))
+        .and_then(|m| m.as_str())
+        .map_or_else(|| message.to_string(), str::to_string)
+}
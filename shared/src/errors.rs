@@ -19,6 +19,10 @@ pub enum ConfluenceError {
     #[error("Page not found: {page_id}")]
     PageNotFound { page_id: String },
 
+    /// Space not found
+    #[error("Space not found: {space_key}")]
+    SpaceNotFound { space_key: String },
+
     /// Label operation failed
     #[error("Label operation failed: {message}")]
     LabelOperation { message: String },
@@ -31,6 +35,25 @@ pub enum ConfluenceError {
     #[error("API error {status}: {message}")]
     ApiError { status: u16, message: String },
 
+    /// API returned a successful response without the expected data
+    #[error("Invalid response: {message}")]
+    InvalidResponse { message: String },
+
+    /// A long-running task finished unsuccessfully
+    #[error("Task {task_id} failed: {}", messages.join("; "))]
+    TaskFailed {
+        task_id: String,
+        messages: Vec<String>,
+    },
+
+    /// A long-running task did not finish in time
+    #[error("Task {task_id} did not finish within {seconds} seconds")]
+    TaskTimedOut { task_id: String, seconds: u64 },
+
+    /// Local file I/O failed
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
     /// Invalid URL provided
     #[error("Invalid URL: {0}")]
     InvalidUrl(#[from] url::ParseError),
@@ -41,3 +64,61 @@ pub enum ConfluenceError {
 }
 
 pub type Result<T> = std::result::Result<T, ConfluenceError>;
+
+/// Errors that can occur when interacting with the Jira API.
+#[derive(Error, Debug)]
+pub enum JiraError {
+    /// HTTP request failed
+    #[error("HTTP request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// JSON serialization/deserialization failed
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// Authentication failed
+    #[error("Authentication failed: {message}")]
+    Authentication { message: String },
+
+    /// Issue not found
+    #[error("Issue not found: {key}")]
+    IssueNotFound { key: String },
+
+    /// Project not found
+    #[error("Project not found: {key}")]
+    ProjectNotFound { key: String },
+
+    /// Agile board not found
+    #[error("Board not found: {id}")]
+    BoardNotFound { id: u64 },
+
+    /// Sprint not found
+    #[error("Sprint not found: {id}")]
+    SprintNotFound { id: u64 },
+
+    /// Field name or ID not known to the site
+    #[error("Unknown field '{name}': {message}")]
+    UnknownField { name: String, message: String },
+
+    /// Value not accepted by a field
+    #[error("Invalid value for {field}: {message}")]
+    FieldValue { field: String, message: String },
+
+    /// JQL query error
+    #[error("JQL query failed: {query} - {message}")]
+    JqlQuery { query: String, message: String },
+
+    /// API returned an error response
+    #[error("API error {status}: {message}")]
+    ApiError { status: u16, message: String },
+
+    /// Invalid URL provided
+    #[error("Invalid URL: {0}")]
+    InvalidUrl(#[from] url::ParseError),
+
+    /// Configuration error
+    #[error("Configuration error: {message}")]
+    Config { message: String },
+}
+
+pub type JiraResult<T> = std::result::Result<T, JiraError>;
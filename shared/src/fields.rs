@@ -0,0 +1,464 @@
+//! Jira field metadata, name resolution and value validation.
+//!
+//! Jira identifies custom fields by IDs like `customfield_10042`. The
+//! [`FieldRegistry`] maps display names to IDs, and create, edit and
+//! transition metadata describe which values a field accepts on a screen.
+//! [`JiraClient::field_value`] turns a value typed by a person into the JSON
+//! Jira expects, rejecting values the field cannot hold.
+
+use crate::adf::markdown_to_adf;
+use crate::errors::{JiraError, JiraResult};
+use crate::jira::{error_message, JiraClient, JiraUser};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// A system or custom Jira field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraField {
+    /// Field ID (e.g., "summary" or "customfield_10042")
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// Whether this is a custom field
+    #[serde(default)]
+    pub custom: bool,
+    /// Value schema, absent for some internal fields
+    pub schema: Option<JiraFieldSchema>,
+}
+
+/// Schema describing the values a field accepts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraFieldSchema {
+    /// Value type (e.g., "string", "number", "array", "option", "user")
+    #[serde(rename = "type")]
+    pub field_type: String,
+    /// Element type for array fields
+    pub items: Option<String>,
+    /// System field this schema belongs to
+    pub system: Option<String>,
+    /// Custom field type key
+    pub custom: Option<String>,
+}
+
+/// A field as it appears on a create, edit or transition screen.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraFieldMeta {
+    /// Field ID, when the metadata is returned as a list
+    #[serde(rename = "fieldId", alias = "key")]
+    pub field_id: Option<String>,
+    /// Field display name
+    pub name: String,
+    /// Whether a value must be given
+    #[serde(default)]
+    pub required: bool,
+    /// Whether Jira fills in a value when none is given
+    #[serde(rename = "hasDefaultValue", default)]
+    pub has_default_value: bool,
+    /// Value schema
+    pub schema: Option<JiraFieldSchema>,
+    /// Values the field accepts, for option-like fields
+    #[serde(rename = "allowedValues")]
+    pub allowed_values: Option<Vec<Value>>,
+}
+
+/// Mapping between field names and IDs, with schemas.
+#[derive(Debug, Clone)]
+pub struct FieldRegistry {
+    fields: Vec<JiraField>,
+    by_id: HashMap<String, usize>,
+    by_name: HashMap<String, Vec<usize>>,
+}
+
+/// Issue type available in a project, from create metadata.
+#[derive(Debug, Clone, Deserialize)]
+struct CreateMetaIssueType {
+    id: String,
+    name: String,
+}
+
+/// Issue types of a project, from create metadata.
+#[derive(Debug, Deserialize)]
+struct CreateMetaIssueTypes {
+    #[serde(rename = "issueTypes", alias = "values", default)]
+    issue_types: Vec<CreateMetaIssueType>,
+}
+
+/// Fields of an issue type, from create metadata.
+#[derive(Debug, Deserialize)]
+struct CreateMetaFields {
+    #[serde(alias = "values", alias = "results", default)]
+    fields: Vec<JiraFieldMeta>,
+}
+
+/// Fields of an issue, from edit metadata.
+#[derive(Debug, Deserialize)]
+struct EditMeta {
+    #[serde(default)]
+    fields: HashMap<String, JiraFieldMeta>,
+}
+
+impl FieldRegistry {
+    /// Build a registry from the site's field list.
+    pub fn new(fields: Vec<JiraField>) -> Self {
+        let mut by_id = HashMap::new();
+        let mut by_name: HashMap<String, Vec<usize>> = HashMap::new();
+        for (i, field) in fields.iter().enumerate() {
+            by_id.insert(field.id.clone(), i);
+            by_name
+                .entry(field.name.to_lowercase())
+                .or_default()
+                .push(i);
+        }
+        FieldRegistry {
+            fields,
+            by_id,
+            by_name,
+        }
+    }
+
+    /// All fields on the site.
+    pub fn fields(&self) -> &[JiraField] {
+        &self.fields
+    }
+
+    /// Find a field by ID or by display name (case-insensitive).
+    pub fn get(&self, name_or_id: &str) -> Option<&JiraField> {
+        self.resolve(name_or_id).ok()
+    }
+
+    /// Find a field by ID or display name, failing when none or several match.
+    pub fn resolve(&self, name_or_id: &str) -> JiraResult<&JiraField> {
+        if let Some(&i) = self.by_id.get(name_or_id) {
+            return Ok(&self.fields[i]);
+        }
+        match self
+            .by_name
+            .get(&name_or_id.to_lowercase())
+            .map(Vec::as_slice)
+        {
+            Some([i]) => Ok(&self.fields[*i]),
+            Some(matches) if !matches.is_empty() => {
+                let ids: Vec<&str> = matches
+                    .iter()
+                    .map(|&i| self.fields[i].id.as_str())
+                    .collect();
+                Err(JiraError::UnknownField {
+                    name: name_or_id.to_string(),
+                    message: format!("name is ambiguous ({}); use the field ID", ids.join(", ")),
+                })
+            }
+            _ => Err(JiraError::UnknownField {
+                name: name_or_id.to_string(),
+                message: "no field has this name or ID".to_string(),
+            }),
+        }
+    }
+
+    /// Display name of a field, falling back to the ID for unknown fields.
+    pub fn name<'a>(&'a self, id: &'a str) -> &'a str {
+        self.by_id
+            .get(id)
+            .map_or(id, |&i| self.fields[i].name.as_str())
+    }
+}
+
+impl JiraClient {
+    /// Get every field defined on the site.
+    pub fn get_fields(&self) -> JiraResult<Vec<JiraField>> {
+        let url = format!("{}/rest/api/3/field", self.config.base_url);
+
+        let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = error_message(response);
+            return Err(JiraError::ApiError {
+                status,
+                message: format!("Failed to get fields: HTTP {status}: {message}"),
+            });
+        }
+
+        Ok(response.json()?)
+    }
+
+    /// Field registry for the site, fetched on first use and cached on the client.
+    pub fn field_registry(&self) -> JiraResult<&FieldRegistry> {
+        if let Some(registry) = self.field_registry.get() {
+            return Ok(registry);
+        }
+        let registry = FieldRegistry::new(self.get_fields()?);
+        Ok(self.field_registry.get_or_init(|| registry))
+    }
+
+    /// Fields on the create screen of an issue type in a project, keyed by field ID.
+    ///
+    /// Returns the issue type ID along with the fields.
+    pub fn get_create_meta(
+        &self,
+        project_key: &str,
+        issue_type: &str,
+    ) -> JiraResult<(String, HashMap<String, JiraFieldMeta>)> {
+        let url = format!(
+            "{}/rest/api/3/issue/createmeta/{}/issuetypes?maxResults=200",
+            self.config.base_url, project_key
+        );
+        let types: CreateMetaIssueTypes = self.get_create_meta_page(&url, project_key)?;
+        let issue_type = types
+            .issue_types
+            .into_iter()
+            .find(|t| t.name.eq_ignore_ascii_case(issue_type) || t.id == issue_type)
+            .ok_or_else(|| JiraError::FieldValue {
+                field: "Issue Type".to_string(),
+                message: format!("'{issue_type}' is not an issue type of project {project_key}"),
+            })?;
+
+        let url = format!(
+            "{}/rest/api/3/issue/createmeta/{}/issuetypes/{}?maxResults=200",
+            self.config.base_url, project_key, issue_type.id
+        );
+        let meta: CreateMetaFields = self.get_create_meta_page(&url, project_key)?;
+        let fields = meta
+            .fields
+            .into_iter()
+            .filter_map(|f| Some((f.field_id.clone()?, f)))
+            .collect();
+        Ok((issue_type.id, fields))
+    }
+
+    /// Fields that can be edited on an issue, keyed by field ID.
+    pub fn get_edit_meta(&self, key: &str) -> JiraResult<HashMap<String, JiraFieldMeta>> {
+        let url = format!("{}/rest/api/3/issue/{}/editmeta", self.config.base_url, key);
+
+        let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+        if response.status() == 404 {
+            return Err(JiraError::IssueNotFound {
+                key: key.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = error_message(response);
+            return Err(JiraError::ApiError {
+                status,
+                message: format!("Failed to get edit metadata of {key}: HTTP {status}: {message}"),
+            });
+        }
+
+        let meta: EditMeta = response.json()?;
+        Ok(meta.fields)
+    }
+
+    /// Find users by display name, email or account ID.
+    pub fn find_users(&self, query: &str) -> JiraResult<Vec<JiraUser>> {
+        let url = format!(
+            "{}/rest/api/3/user/search?query={}",
+            self.config.base_url,
+            urlencoding::encode(query)
+        );
+
+        let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = error_message(response);
+            return Err(JiraError::ApiError {
+                status,
+                message: format!("Failed to search users: HTTP {status}: {message}"),
+            });
+        }
+
+        Ok(response.json()?)
+    }
+
+    /// Convert a value given by a person into the JSON a field expects.
+    ///
+    /// Strings are shaped by the field schema: option fields and named
+    /// entities such as priorities are checked against the allowed values in
+    /// `meta`, users are looked up by name or email, numbers and dates are
+    /// checked for format, and array fields take a comma-separated list.
+    /// Strings that are JSON objects or arrays, and non-string values, are
+    /// passed through unchanged.
+    pub fn field_value(
+        &self,
+        field: &JiraField,
+        value: &Value,
+        meta: Option<&JiraFieldMeta>,
+    ) -> JiraResult<Value> {
+        let Value::String(raw) = value else {
+            return Ok(value.clone());
+        };
+        let trimmed = raw.trim_start();
+        if trimmed.starts_with('{') || trimmed.starts_with('[') {
+            if let Ok(value) = serde_json::from_str(raw) {
+                return Ok(value);
+            }
+        }
+
+        let schema = meta
+            .and_then(|m| m.schema.as_ref())
+            .or(field.schema.as_ref());
+        let Some(schema) = schema else {
+            return Ok(value.clone());
+        };
+        let allowed = meta.and_then(|m| m.allowed_values.as_deref());
+        match schema.field_type.as_str() {
+            "array" => {
+                let item_type = schema.items.as_deref().unwrap_or("string");
+                let items = raw
+                    .split(',')
+                    .map(|s| s.trim())
+                    .filter(|s| !s.is_empty())
+                    .map(|s| self.scalar_value(field, item_type, s, allowed))
+                    .collect::<JiraResult<Vec<_>>>()?;
+                Ok(Value::Array(items))
+            }
+            field_type => self.scalar_value(field, field_type, raw, allowed),
+        }
+    }
+
+    /// Shape and validate a single value for a schema type.
+    fn scalar_value(
+        &self,
+        field: &JiraField,
+        field_type: &str,
+        raw: &str,
+        allowed: Option<&[Value]>,
+    ) -> JiraResult<Value> {
+        let invalid = |message: String| JiraError::FieldValue {
+            field: field.name.clone(),
+            message,
+        };
+        match field_type {
+            "number" => match (raw.parse::<i64>(), raw.parse::<f64>()) {
+                (Ok(n), _) => Ok(Value::from(n)),
+                (_, Ok(n)) => Ok(Value::from(n)),
+                _ => Err(invalid(format!("'{raw}' is not a number"))),
+            },
+            "date" => {
+                if is_date(raw) {
+                    Ok(json!(raw))
+                } else {
+                    Err(invalid(format!("'{raw}' is not a date (YYYY-MM-DD)")))
+                }
+            }
+            "datetime" => {
+                if is_date(raw) {
+                    Ok(json!(format!("{raw}T00:00:00.000+0000")))
+                } else if raw.get(..10).is_some_and(is_date) && raw[10..].starts_with('T') {
+                    Ok(json!(raw))
+                } else {
+                    Err(invalid(format!(
+                        "'{raw}' is not a date-time (YYYY-MM-DDThh:mm:ss.sss+0000)"
+                    )))
+                }
+            }
+            "doc" => Ok(markdown_to_adf(raw)),
+            "user" => {
+                let account_id = self.resolve_user(raw).map_err(invalid)?;
+                Ok(json!({ "accountId": account_id }))
+            }
+            "project" | "issuelink" => Ok(json!({ "key": raw })),
+            "option" | "priority" | "issuetype" | "resolution" | "version" | "component"
+            | "status" | "securitylevel" => match allowed {
+                Some(allowed) => {
+                    let found = allowed.iter().find(|v| {
+                        ["value", "name", "id"]
+                            .iter()
+                            .any(|k| v[*k].as_str().is_some_and(|s| s.eq_ignore_ascii_case(raw)))
+                    });
+                    match found.and_then(|v| v["id"].as_str()) {
+                        Some(id) => Ok(json!({ "id": id })),
+                        None => {
+                            let names: Vec<&str> = allowed.iter().filter_map(value_label).collect();
+                            Err(invalid(format!(
+                                "'{raw}' is not allowed; expected one of: {}",
+                                names.join(", ")
+                            )))
+                        }
+                    }
+                }
+                None if field_type == "option" => Ok(json!({ "value": raw })),
+                None => Ok(json!({ "name": raw })),
+            },
+            _ => Ok(json!(raw)),
+        }
+    }
+
+    /// Resolve a user given by account ID, display name or email to an account ID.
+    fn resolve_user(&self, raw: &str) -> Result<String, String> {
+        let users = self.find_users(raw).map_err(|e| e.to_string())?;
+        let matches: Vec<&JiraUser> = users
+            .iter()
+            .filter(|u| {
+                u.account_id.as_deref() == Some(raw)
+                    || u.display_name
+                        .as_deref()
+                        .is_some_and(|n| n.eq_ignore_ascii_case(raw))
+                    || u.email_address
+                        .as_deref()
+                        .is_some_and(|e| e.eq_ignore_ascii_case(raw))
+            })
+            .collect();
+        match matches.as_slice() {
+            [user] => Ok(user.account_id.clone().unwrap_or_default()),
+            [] => Err(format!("no user matches '{raw}'")),
+            _ => Err(format!(
+                "'{raw}' matches {} users; use the account ID",
+                matches.len()
+            )),
+        }
+    }
+
+    /// Fetch a create metadata document, mapping a 404 to a missing project.
+    fn get_create_meta_page<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+        project_key: &str,
+    ) -> JiraResult<T> {
+        let response = self.client.get(url).headers(self.headers.clone()).send()?;
+
+        if response.status() == 404 {
+            return Err(JiraError::ProjectNotFound {
+                key: project_key.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = error_message(response);
+            return Err(JiraError::ApiError {
+                status,
+                message: format!(
+                    "Failed to get create metadata of {project_key}: HTTP {status}: {message}"
+                ),
+            });
+        }
+
+        Ok(response.json()?)
+    }
+}
+
+/// Human-readable label of an allowed value.
+fn value_label(value: &Value) -> Option<&str> {
+    value["value"]
+        .as_str()
+        .or_else(|| value["name"].as_str())
+        .or_else(|| value["id"].as_str())
+}
+
+/// Whether a string is a calendar date in the form YYYY-MM-DD.
+fn is_date(s: &str) -> bool {
+    let parts: Vec<&str> = s.split('-').collect();
+    let [year, month, day] = parts.as_slice() else {
+        return false;
+    };
+    let numeric = |p: &str, len: usize| p.len() == len && p.bytes().all(|b| b.is_ascii_digit());
+    if !numeric(year, 4) || !numeric(month, 2) || !numeric(day, 2) {
+        return false;
+    }
+    let (month, day): (u32, u32) = (month.parse().unwrap_or(0), day.parse().unwrap_or(0));
+    (1..=12).contains(&month) && (1..=31).contains(&day)
+}
@@ -0,0 +1,93 @@
+//! Page version history.
+
+use crate::confluence::{ConfluenceClient, ConfluencePage, SearchLinks};
+use crate::content::PageVersion;
+use crate::errors::{ConfluenceError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Response from the content versions endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VersionsResponse {
+    results: Vec<PageVersion>,
+    #[serde(rename = "_links")]
+    links: Option<SearchLinks>,
+}
+
+impl ConfluenceClient {
+    /// List every version of a page, newest first.
+    pub fn get_page_versions(&self, page_id: &str) -> Result<Vec<PageVersion>> {
+        let mut url = format!(
+            "{}/wiki/rest/api/content/{}/version?limit=200",
+            self.config.base_url, page_id
+        );
+        let mut versions = Vec::new();
+
+        loop {
+            let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+            if response.status() == 404 {
+                return Err(ConfluenceError::PageNotFound {
+                    page_id: page_id.to_string(),
+                });
+            }
+
+            if !response.status().is_success() {
+                let status = response.status().as_u16();
+                let error_text = response
+                    .text()
+                    .unwrap_or_else(|_| "Unknown error".to_string());
+                return Err(ConfluenceError::ApiError {
+                    status,
+                    message: format!(
+                        "Failed to list versions of page {page_id}: HTTP {status}: {error_text}"
+                    ),
+                });
+            }
+
+            let page: VersionsResponse = response.json()?;
+            versions.extend(page.results);
+
+            match page.links.and_then(|l| l.next) {
+                Some(next) => url = format!("{}/wiki{}", self.config.base_url, next),
+                None => break,
+            }
+        }
+
+        versions.sort_by_key(|v| std::cmp::Reverse(v.number));
+        Ok(versions)
+    }
+
+    /// Fetch a page as it was at the given version, including its body and labels.
+    ///
+    /// Confluence does not version labels, so the labels returned are the ones
+    /// the API reports alongside that version.
+    pub fn get_page_at_version(&self, page_id: &str, version: i32) -> Result<ConfluencePage> {
+        let url = format!(
+            "{}/wiki/rest/api/content/{}?status=historical&version={}&expand=body.storage,version,metadata.labels",
+            self.config.base_url, page_id, version
+        );
+
+        let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+        if response.status() == 404 {
+            return Err(ConfluenceError::PageNotFound {
+                page_id: format!("{page_id} (version {version})"),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!(
+                    "Failed to get version {version} of page {page_id}: HTTP {status}: {error_text}"
+                ),
+            });
+        }
+
+        Ok(response.json()?)
+    }
+}
@@ -0,0 +1,105 @@
+//! Jira issue comments.
+
+use crate::errors::{JiraError, JiraResult};
+use crate::jira::{error_message, JiraClient, JiraUser};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Page size used when listing comments.
+const COMMENT_PAGE_SIZE: usize = 100;
+
+/// A comment on a Jira issue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraComment {
+    /// Comment ID
+    pub id: String,
+    /// User who wrote the comment
+    pub author: Option<JiraUser>,
+    /// Comment body in Atlassian Document Format
+    pub body: Option<Value>,
+    /// When the comment was written
+    pub created: Option<String>,
+    /// When the comment was last edited
+    pub updated: Option<String>,
+    /// Whether a service desk customer can see the comment
+    #[serde(rename = "jsdPublic", skip_serializing_if = "Option::is_none")]
+    pub jsd_public: Option<bool>,
+}
+
+/// A page of comments.
+#[derive(Debug, Deserialize)]
+struct CommentPage {
+    #[serde(default)]
+    total: usize,
+    #[serde(default)]
+    comments: Vec<JiraComment>,
+}
+
+impl JiraClient {
+    /// Get every comment on an issue, oldest first.
+    pub fn get_comments(&self, key: &str) -> JiraResult<Vec<JiraComment>> {
+        let mut comments = Vec::new();
+        loop {
+            let url = format!(
+                "{}/rest/api/3/issue/{}/comment?startAt={}&maxResults={COMMENT_PAGE_SIZE}&orderBy=created",
+                self.config.base_url,
+                key,
+                comments.len()
+            );
+
+            let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+            if response.status() == 404 {
+                return Err(JiraError::IssueNotFound {
+                    key: key.to_string(),
+                });
+            }
+
+            if !response.status().is_success() {
+                let status = response.status().as_u16();
+                let message = error_message(response);
+                return Err(JiraError::ApiError {
+                    status,
+                    message: format!("Failed to get comments of {key}: HTTP {status}: {message}"),
+                });
+            }
+
+            let page: CommentPage = response.json()?;
+            let received = page.comments.len();
+            comments.extend(page.comments);
+            if received == 0 || comments.len() >= page.total {
+                break;
+            }
+        }
+        Ok(comments)
+    }
+
+    /// Add a comment to an issue; `body` is an Atlassian Document Format document.
+    pub fn add_comment(&self, key: &str, body: &Value) -> JiraResult<JiraComment> {
+        let url = format!("{}/rest/api/3/issue/{}/comment", self.config.base_url, key);
+
+        let response = self
+            .client
+            .post(&url)
+            .headers(self.headers.clone())
+            .json(&serde_json::json!({ "body": body }))
+            .send()?;
+
+        if response.status() == 404 {
+            return Err(JiraError::IssueNotFound {
+                key: key.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = error_message(response);
+            return Err(JiraError::ApiError {
+                status,
+                message: format!("Failed to comment on {key}: HTTP {status}: {message}"),
+            });
+        }
+
+        Ok(response.json()?)
+    }
+}
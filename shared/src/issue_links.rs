@@ -0,0 +1,478 @@
+//! Jira issue links, and dependency graphs built by following them.
+//!
+//! [`JiraClient::get_issue_graph`] walks issue links, subtasks and parents
+//! outward from a set of root issues. Only "Blocks" links count as
+//! dependencies when looking for cycles and blocked-by chains; other link
+//! types are kept in the graph for context.
+
+use crate::errors::{JiraError, JiraResult};
+use crate::jira::{JiraClient, JiraIssue, JiraSearchOptions};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Fields fetched for every issue in a graph.
+const GRAPH_FIELDS: [&str; 6] = [
+    "summary",
+    "status",
+    "issuetype",
+    "issuelinks",
+    "subtasks",
+    "parent",
+];
+
+/// Number of issues fetched per JQL query while walking the graph.
+const GRAPH_BATCH_SIZE: usize = 50;
+
+/// Link type whose links are dependencies.
+const BLOCKS_LINK_TYPE: &str = "Blocks";
+
+/// Link type name used for subtask and parent edges.
+const PARENT_LINK_TYPE: &str = "Parent";
+
+/// A link between two issues, as found in an issue's `issuelinks` field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraIssueLink {
+    /// Link ID
+    pub id: Option<String>,
+    /// Link type
+    #[serde(rename = "type")]
+    pub link_type: JiraIssueLinkType,
+    /// The linked issue, when the link points at this issue
+    #[serde(rename = "inwardIssue")]
+    pub inward_issue: Option<JiraIssue>,
+    /// The linked issue, when the link points away from this issue
+    #[serde(rename = "outwardIssue")]
+    pub outward_issue: Option<JiraIssue>,
+}
+
+/// Type of an issue link.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraIssueLinkType {
+    /// Link type ID
+    pub id: Option<String>,
+    /// Link type name (e.g., "Blocks")
+    pub name: String,
+    /// Description from the inward side (e.g., "is blocked by")
+    pub inward: Option<String>,
+    /// Description from the outward side (e.g., "blocks")
+    pub outward: Option<String>,
+}
+
+/// Options for walking an issue graph.
+#[derive(Debug, Clone, Default)]
+pub struct IssueGraphOptions {
+    /// Number of links to follow from the roots; `None` follows every link
+    pub max_depth: Option<usize>,
+    /// Link type names to follow (case-insensitive); empty follows all
+    pub link_types: Vec<String>,
+    /// Whether to follow subtasks and parents
+    pub include_parents: bool,
+}
+
+/// An issue in a graph.
+#[derive(Debug, Clone, Serialize)]
+pub struct IssueNode {
+    /// Issue key
+    pub key: String,
+    /// Issue summary
+    pub summary: String,
+    /// Status name
+    pub status: Option<String>,
+    /// Status category key ("new", "indeterminate" or "done")
+    pub status_category: Option<String>,
+    /// Issue type name
+    pub issue_type: Option<String>,
+    /// Whether the issue was one of the roots
+    pub root: bool,
+}
+
+/// A directed edge between two issues.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct IssueEdge {
+    /// Issue on the outward side (e.g., the blocking issue)
+    pub from: String,
+    /// Issue on the inward side (e.g., the blocked issue)
+    pub to: String,
+    /// Link type name, or "Parent" for subtask and parent edges
+    pub link_type: String,
+    /// Outward description of the link (e.g., "blocks")
+    pub relation: String,
+}
+
+/// Issues and the links between them.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IssueGraph {
+    /// Issues by key
+    pub nodes: BTreeMap<String, IssueNode>,
+    /// Links between issues, without duplicates
+    pub edges: Vec<IssueEdge>,
+}
+
+impl IssueEdge {
+    /// Whether this edge is a dependency: the `from` issue blocks the `to` issue.
+    pub fn is_blocking(&self) -> bool {
+        self.link_type.eq_ignore_ascii_case(BLOCKS_LINK_TYPE)
+    }
+}
+
+impl IssueGraph {
+    /// Edges that are dependencies.
+    pub fn blocking_edges(&self) -> impl Iterator<Item = &IssueEdge> {
+        self.edges.iter().filter(|e| e.is_blocking())
+    }
+
+    /// Groups of issues that block each other in a cycle, each sorted by key.
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        let mut tarjan = Tarjan::new(self);
+        for key in self.nodes.keys() {
+            if !tarjan.index.contains_key(key.as_str()) {
+                tarjan.visit(key);
+            }
+        }
+
+        let self_loops: BTreeSet<&str> = self
+            .blocking_edges()
+            .filter(|e| e.from == e.to)
+            .map(|e| e.from.as_str())
+            .collect();
+        let mut cycles: Vec<Vec<String>> = tarjan
+            .components
+            .into_iter()
+            .filter(|c| c.len() > 1 || self_loops.contains(c[0]))
+            .map(|c| {
+                let mut cycle: Vec<String> = c.into_iter().map(str::to_string).collect();
+                cycle.sort();
+                cycle
+            })
+            .collect();
+        cycles.sort();
+        cycles
+    }
+
+    /// The longest chain of issues each blocked by the next, starting with
+    /// the most blocked issue.
+    ///
+    /// Links between issues of the same cycle are ignored, so the result is
+    /// well defined even when [`IssueGraph::cycles`] is not empty.
+    pub fn longest_blocked_chain(&self) -> Vec<String> {
+        let cycles = self.cycles();
+        let component: HashMap<&str, usize> = cycles
+            .iter()
+            .enumerate()
+            .flat_map(|(i, cycle)| cycle.iter().map(move |key| (key.as_str(), i)))
+            .collect();
+        let same_cycle = |a: &str, b: &str| {
+            component
+                .get(a)
+                .is_some_and(|c| component.get(b) == Some(c))
+        };
+
+        let mut blocked: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        let mut blockers: HashMap<&str, usize> =
+            self.nodes.keys().map(|k| (k.as_str(), 0)).collect();
+        for edge in self.blocking_edges() {
+            if edge.from == edge.to || same_cycle(&edge.from, &edge.to) {
+                continue;
+            }
+            blocked.entry(&edge.from).or_default().push(&edge.to);
+            *blockers.entry(&edge.to).or_default() += 1;
+        }
+
+        // Longest path in the remaining DAG, in topological order (Kahn)
+        let mut ready: Vec<&str> = self
+            .nodes
+            .keys()
+            .map(String::as_str)
+            .filter(|k| blockers[k] == 0)
+            .collect();
+        ready.reverse();
+        let mut length: HashMap<&str, usize> = HashMap::new();
+        let mut previous: HashMap<&str, &str> = HashMap::new();
+        while let Some(key) = ready.pop() {
+            let here = length.get(key).copied().unwrap_or(1);
+            length.insert(key, here);
+            for &next in blocked.get(key).into_iter().flatten() {
+                if here + 1 > length.get(next).copied().unwrap_or(1) {
+                    length.insert(next, here + 1);
+                    previous.insert(next, key);
+                }
+                let remaining = blockers.get_mut(next).expect("every edge end is counted");
+                *remaining -= 1;
+                if *remaining == 0 {
+                    ready.push(next);
+                }
+            }
+        }
+
+        let Some((&last, _)) = length
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+        else {
+            return Vec::new();
+        };
+        let mut chain = vec![last.to_string()];
+        let mut current = last;
+        while let Some(&blocker) = previous.get(current) {
+            chain.push(blocker.to_string());
+            current = blocker;
+        }
+        if chain.len() < 2 {
+            return Vec::new();
+        }
+        chain
+    }
+
+    /// Add an issue, keeping what is already known about it.
+    fn add_node(&mut self, issue: &JiraIssue, root: bool) {
+        let fields = &issue.fields;
+        let node = self
+            .nodes
+            .entry(issue.key.clone())
+            .or_insert_with(|| IssueNode {
+                key: issue.key.clone(),
+                summary: String::new(),
+                status: None,
+                status_category: None,
+                issue_type: None,
+                root,
+            });
+        node.root |= root;
+        if let Some(summary) = &fields.summary {
+            node.summary = summary.clone();
+        }
+        if let Some(status) = &fields.status {
+            node.status = Some(status.name.clone());
+            node.status_category = status.category.as_ref().map(|c| c.key.clone());
+        }
+        if let Some(issue_type) = &fields.issue_type {
+            node.issue_type = Some(issue_type.name.clone());
+        }
+    }
+}
+
+/// Tarjan's strongly connected components over blocking edges.
+struct Tarjan<'a> {
+    successors: HashMap<&'a str, Vec<&'a str>>,
+    index: HashMap<&'a str, usize>,
+    low: HashMap<&'a str, usize>,
+    stack: Vec<&'a str>,
+    on_stack: BTreeSet<&'a str>,
+    components: Vec<Vec<&'a str>>,
+}
+
+impl<'a> Tarjan<'a> {
+    fn new(graph: &'a IssueGraph) -> Self {
+        let mut successors: HashMap<&str, Vec<&str>> = HashMap::new();
+        for edge in graph.blocking_edges() {
+            successors.entry(&edge.from).or_default().push(&edge.to);
+        }
+        Tarjan {
+            successors,
+            index: HashMap::new(),
+            low: HashMap::new(),
+            stack: Vec::new(),
+            on_stack: BTreeSet::new(),
+            components: Vec::new(),
+        }
+    }
+
+    fn visit(&mut self, key: &'a str) {
+        let index = self.index.len();
+        self.index.insert(key, index);
+        self.low.insert(key, index);
+        self.stack.push(key);
+        self.on_stack.insert(key);
+
+        for next in self.successors.get(key).cloned().unwrap_or_default() {
+            if !self.index.contains_key(next) {
+                self.visit(next);
+                let low = self.low[key].min(self.low[next]);
+                self.low.insert(key, low);
+            } else if self.on_stack.contains(next) {
+                let low = self.low[key].min(self.index[next]);
+                self.low.insert(key, low);
+            }
+        }
+
+        if self.low[key] == index {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack.remove(member);
+                component.push(member);
+                if member == key {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+impl JiraClient {
+    /// Get the links of an issue.
+    pub fn get_issue_links(&self, key: &str) -> JiraResult<Vec<JiraIssueLink>> {
+        let issue = self.get_issue(key, &["issuelinks".to_string()], &[])?;
+        Ok(issue_links(&issue))
+    }
+
+    /// Build the graph of the issues matching a JQL query and everything
+    /// linked to them.
+    pub fn get_issue_graph_for_jql(
+        &self,
+        jql: &str,
+        options: &IssueGraphOptions,
+    ) -> JiraResult<IssueGraph> {
+        let search = JiraSearchOptions {
+            fields: vec!["summary".to_string()],
+            ..JiraSearchOptions::default()
+        };
+        let roots: Vec<String> = self
+            .search_issues(jql, &search)?
+            .into_iter()
+            .map(|issue| issue.key)
+            .collect();
+        self.get_issue_graph(&roots, options)
+    }
+
+    /// Build the graph of issues reachable from `roots` through issue links,
+    /// and through subtasks and parents when `options.include_parents` is set.
+    pub fn get_issue_graph(
+        &self,
+        roots: &[String],
+        options: &IssueGraphOptions,
+    ) -> JiraResult<IssueGraph> {
+        let follows = |link_type: &str| {
+            options.link_types.is_empty()
+                || options
+                    .link_types
+                    .iter()
+                    .any(|t| t.eq_ignore_ascii_case(link_type))
+        };
+
+        let mut graph = IssueGraph::default();
+        let mut edges = BTreeSet::new();
+        let mut seen: BTreeSet<String> = roots.iter().cloned().collect();
+        let mut frontier: Vec<String> = seen.iter().cloned().collect();
+        let mut depth = 0;
+
+        while !frontier.is_empty() {
+            let mut next = Vec::new();
+            for issue in self.get_graph_issues(&frontier)? {
+                graph.add_node(&issue, roots.contains(&issue.key));
+                let mut neighbours = Vec::new();
+
+                for link in issue_links(&issue) {
+                    if !follows(&link.link_type.name) {
+                        continue;
+                    }
+                    let relation = link
+                        .link_type
+                        .outward
+                        .clone()
+                        .unwrap_or_else(|| link.link_type.name.clone());
+                    let (from, to, other) = match (&link.outward_issue, &link.inward_issue) {
+                        (Some(other), _) => (&issue.key, &other.key, other),
+                        (None, Some(other)) => (&other.key, &issue.key, other),
+                        (None, None) => continue,
+                    };
+                    edges.insert(IssueEdge {
+                        from: from.clone(),
+                        to: to.clone(),
+                        link_type: link.link_type.name.clone(),
+                        relation,
+                    });
+                    neighbours.push(other.clone());
+                }
+
+                if options.include_parents {
+                    let subtasks: Vec<JiraIssue> = issue
+                        .fields
+                        .other
+                        .get("subtasks")
+                        .and_then(|s| serde_json::from_value(s.clone()).ok())
+                        .unwrap_or_default();
+                    for subtask in subtasks {
+                        edges.insert(parent_edge(&issue.key, &subtask));
+                        neighbours.push(subtask);
+                    }
+                    if let Some(parent) = &issue.fields.parent {
+                        edges.insert(parent_edge(&parent.key, &issue));
+                        neighbours.push((**parent).clone());
+                    }
+                }
+
+                for other in neighbours {
+                    graph.add_node(&other, false);
+                    if options.max_depth.is_none_or(|max| depth < max)
+                        && seen.insert(other.key.clone())
+                    {
+                        next.push(other.key);
+                    }
+                }
+            }
+            frontier = next;
+            depth += 1;
+        }
+
+        graph.edges = edges.into_iter().collect();
+        Ok(graph)
+    }
+
+    /// Fetch issues with the fields needed for a graph.
+    ///
+    /// Issues are fetched in batches by JQL; a batch naming an issue that
+    /// cannot be seen is rejected as a whole, so it is retried one issue at
+    /// a time and missing issues are skipped.
+    fn get_graph_issues(&self, keys: &[String]) -> JiraResult<Vec<JiraIssue>> {
+        let fields: Vec<String> = GRAPH_FIELDS.map(str::to_string).to_vec();
+        let options = JiraSearchOptions {
+            fields: fields.clone(),
+            ..JiraSearchOptions::default()
+        };
+
+        let mut issues = Vec::new();
+        for batch in keys.chunks(GRAPH_BATCH_SIZE) {
+            let jql = format!("key in ({})", batch.join(", "));
+            match self.search_issues(&jql, &options) {
+                Ok(found) => issues.extend(found),
+                Err(JiraError::JqlQuery { .. }) => {
+                    for key in batch {
+                        match self.get_issue(key, &fields, &[]) {
+                            Ok(issue) => issues.push(issue),
+                            Err(JiraError::IssueNotFound { .. }) => {}
+                            Err(e) => return Err(e),
+                        }
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(issues)
+    }
+}
+
+/// The links in an issue's `issuelinks` field.
+fn issue_links(issue: &JiraIssue) -> Vec<JiraIssueLink> {
+    issue
+        .fields
+        .other
+        .get("issuelinks")
+        .and_then(|links| serde_json::from_value(links.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Edge from a parent issue to one of its children.
+fn parent_edge(parent: &str, child: &JiraIssue) -> IssueEdge {
+    let subtask = child.fields.issue_type.as_ref().is_some_and(|t| t.subtask);
+    IssueEdge {
+        from: parent.to_string(),
+        to: child.key.clone(),
+        link_type: PARENT_LINK_TYPE.to_string(),
+        relation: if subtask {
+            "has subtask"
+        } else {
+            "is parent of"
+        }
+        .to_string(),
+    }
+}
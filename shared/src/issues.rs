@@ -0,0 +1,182 @@
+//! Jira issue create, read, update and delete.
+
+use crate::errors::{JiraError, JiraResult};
+use crate::jira::{error_message, JiraClient, JiraIssue};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Reference to an issue returned when it is created.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraCreatedIssue {
+    /// Issue ID
+    pub id: String,
+    /// Issue key
+    pub key: String,
+    /// API self link
+    #[serde(rename = "self")]
+    pub self_link: Option<String>,
+}
+
+impl JiraClient {
+    /// Get an issue by key or ID.
+    ///
+    /// An empty `fields` list returns all fields.
+    pub fn get_issue(
+        &self,
+        key: &str,
+        fields: &[String],
+        expand: &[String],
+    ) -> JiraResult<JiraIssue> {
+        let mut url = format!("{}/rest/api/3/issue/{}", self.config.base_url, key);
+        let mut params = Vec::new();
+        if !fields.is_empty() {
+            params.push(format!("fields={}", urlencoding::encode(&fields.join(","))));
+        }
+        if !expand.is_empty() {
+            params.push(format!("expand={}", urlencoding::encode(&expand.join(","))));
+        }
+        if !params.is_empty() {
+            url.push('?');
+            url.push_str(&params.join("&"));
+        }
+
+        let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+        if response.status() == 404 {
+            return Err(JiraError::IssueNotFound {
+                key: key.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = error_message(response);
+            return Err(JiraError::ApiError {
+                status,
+                message: format!("Failed to get issue {key}: HTTP {status}: {message}"),
+            });
+        }
+
+        Ok(response.json()?)
+    }
+
+    /// Create an issue from a map of field IDs to values.
+    pub fn create_issue(&self, fields: &Map<String, Value>) -> JiraResult<JiraCreatedIssue> {
+        let url = format!("{}/rest/api/3/issue", self.config.base_url);
+        let body = serde_json::json!({ "fields": fields });
+
+        let response = self
+            .client
+            .post(&url)
+            .headers(self.headers.clone())
+            .json(&body)
+            .send()?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = error_message(response);
+            return Err(JiraError::ApiError {
+                status,
+                message: format!("Failed to create issue: HTTP {status}: {message}"),
+            });
+        }
+
+        Ok(response.json()?)
+    }
+
+    /// Set fields on an existing issue.
+    pub fn update_issue(&self, key: &str, fields: &Map<String, Value>) -> JiraResult<()> {
+        let url = format!("{}/rest/api/3/issue/{}", self.config.base_url, key);
+        let body = serde_json::json!({ "fields": fields });
+
+        let response = self
+            .client
+            .put(&url)
+            .headers(self.headers.clone())
+            .json(&body)
+            .send()?;
+
+        if response.status() == 404 {
+            return Err(JiraError::IssueNotFound {
+                key: key.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = error_message(response);
+            return Err(JiraError::ApiError {
+                status,
+                message: format!("Failed to update issue {key}: HTTP {status}: {message}"),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Add and remove labels on an issue in one request.
+    pub fn update_issue_labels(&self, key: &str, add: &[&str], remove: &[&str]) -> JiraResult<()> {
+        let url = format!("{}/rest/api/3/issue/{}", self.config.base_url, key);
+        let operations: Vec<Value> = remove
+            .iter()
+            .map(|label| serde_json::json!({ "remove": label }))
+            .chain(add.iter().map(|label| serde_json::json!({ "add": label })))
+            .collect();
+        let body = serde_json::json!({ "update": { "labels": operations } });
+
+        let response = self
+            .client
+            .put(&url)
+            .headers(self.headers.clone())
+            .json(&body)
+            .send()?;
+
+        if response.status() == 404 {
+            return Err(JiraError::IssueNotFound {
+                key: key.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = error_message(response);
+            return Err(JiraError::ApiError {
+                status,
+                message: format!("Failed to update labels on {key}: HTTP {status}: {message}"),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Delete an issue, optionally together with its subtasks.
+    pub fn delete_issue(&self, key: &str, delete_subtasks: bool) -> JiraResult<()> {
+        let url = format!(
+            "{}/rest/api/3/issue/{}?deleteSubtasks={delete_subtasks}",
+            self.config.base_url, key
+        );
+
+        let response = self
+            .client
+            .delete(&url)
+            .headers(self.headers.clone())
+            .send()?;
+
+        if response.status() == 404 {
+            return Err(JiraError::IssueNotFound {
+                key: key.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = error_message(response);
+            return Err(JiraError::ApiError {
+                status,
+                message: format!("Failed to delete issue {key}: HTTP {status}: {message}"),
+            });
+        }
+
+        Ok(())
+    }
+}
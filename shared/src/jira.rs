@@ -0,0 +1,305 @@
+//! Jira Cloud REST API client.
+//!
+//! Jira lives on the same Atlassian site as Confluence and accepts the same
+//! basic auth credentials, so the client is built from a `ConfluenceConfig`.
+
+use crate::confluence::{auth_headers, ConfluenceConfig};
+use crate::errors::{JiraError, JiraResult};
+use crate::fields::FieldRegistry;
+use reqwest::blocking::{Client, Response};
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use url::Url;
+
+/// Largest page size Jira accepts for issue search.
+const MAX_SEARCH_PAGE_SIZE: usize = 100;
+
+/// Client for the Jira Cloud REST API (v3).
+pub struct JiraClient {
+    pub(crate) client: Client,
+    pub(crate) config: ConfluenceConfig,
+    pub(crate) headers: HeaderMap,
+    pub(crate) field_registry: OnceCell<FieldRegistry>,
+}
+
+/// Represents a Jira issue returned from the API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraIssue {
+    /// Issue ID
+    pub id: String,
+    /// Issue key (e.g., "OPS-123")
+    pub key: String,
+    /// API self link
+    #[serde(rename = "self")]
+    pub self_link: Option<String>,
+    /// Issue fields; only the requested fields are present
+    #[serde(default)]
+    pub fields: JiraIssueFields,
+    /// Field values rendered as HTML (requires `renderedFields` expansion)
+    #[serde(rename = "renderedFields")]
+    pub rendered_fields: Option<HashMap<String, Value>>,
+    /// Change history (requires `changelog` expansion)
+    pub changelog: Option<Value>,
+    /// Field ID -> display name (requires `names` expansion)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub names: Option<HashMap<String, String>>,
+}
+
+/// Fields of a Jira issue.
+///
+/// Well-known system fields are typed; everything else, including custom
+/// fields, is kept in `other` keyed by field ID.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JiraIssueFields {
+    /// Issue summary
+    pub summary: Option<String>,
+    /// Issue description in Atlassian Document Format
+    pub description: Option<Value>,
+    /// Issue status
+    pub status: Option<JiraStatus>,
+    /// Issue type
+    #[serde(rename = "issuetype")]
+    pub issue_type: Option<JiraIssueType>,
+    /// Project the issue belongs to
+    pub project: Option<JiraProjectRef>,
+    /// Issue priority
+    pub priority: Option<JiraPriority>,
+    /// Assigned user
+    pub assignee: Option<JiraUser>,
+    /// Reporting user
+    pub reporter: Option<JiraUser>,
+    /// Issue labels
+    pub labels: Option<Vec<String>>,
+    /// Parent issue, for subtasks and issues below an epic
+    pub parent: Option<Box<JiraIssue>>,
+    /// Creation timestamp
+    pub created: Option<String>,
+    /// Last update timestamp
+    pub updated: Option<String>,
+    /// Any other requested fields, keyed by field ID
+    #[serde(flatten)]
+    pub other: HashMap<String, Value>,
+}
+
+/// Status of a Jira issue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraStatus {
+    /// Status ID
+    pub id: Option<String>,
+    /// Status name (e.g., "In Progress")
+    pub name: String,
+    /// Status category
+    #[serde(rename = "statusCategory")]
+    pub category: Option<JiraStatusCategory>,
+}
+
+/// Category grouping statuses into to-do, in progress and done.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraStatusCategory {
+    /// Category key ("new", "indeterminate" or "done")
+    pub key: String,
+    /// Category name
+    pub name: Option<String>,
+}
+
+/// Type of a Jira issue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraIssueType {
+    /// Issue type ID
+    pub id: Option<String>,
+    /// Issue type name (e.g., "Bug")
+    pub name: String,
+    /// Whether this is a subtask type
+    #[serde(default)]
+    pub subtask: bool,
+}
+
+/// Reference to a Jira project embedded in other resources.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraProjectRef {
+    /// Project ID
+    pub id: String,
+    /// Project key
+    pub key: String,
+    /// Project name
+    pub name: Option<String>,
+}
+
+/// Priority of a Jira issue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraPriority {
+    /// Priority ID
+    pub id: Option<String>,
+    /// Priority name (e.g., "High")
+    pub name: String,
+}
+
+/// A Jira user.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraUser {
+    /// Atlassian account ID
+    #[serde(rename = "accountId")]
+    pub account_id: Option<String>,
+    /// Display name
+    #[serde(rename = "displayName")]
+    pub display_name: Option<String>,
+    /// Email address, if visible to the caller
+    #[serde(rename = "emailAddress")]
+    pub email_address: Option<String>,
+}
+
+/// One page of results from a JQL search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraSearchResponse {
+    /// Index of the first result
+    #[serde(rename = "startAt")]
+    pub start_at: usize,
+    /// Page size used by the server
+    #[serde(rename = "maxResults")]
+    pub max_results: usize,
+    /// Total number of matching issues
+    pub total: usize,
+    /// Issues on this page
+    #[serde(default)]
+    pub issues: Vec<JiraIssue>,
+    /// Field ID -> display name (requires `names` expansion)
+    pub names: Option<HashMap<String, String>>,
+}
+
+/// Options for a JQL search.
+#[derive(Debug, Clone, Default)]
+pub struct JiraSearchOptions {
+    /// Fields to return; empty returns Jira's default navigable fields
+    pub fields: Vec<String>,
+    /// Expansions (e.g., "renderedFields", "changelog", "names")
+    pub expand: Vec<String>,
+    /// Maximum number of issues to return across all pages
+    pub limit: Option<usize>,
+}
+
+/// Error body returned by Jira.
+#[derive(Debug, Deserialize)]
+struct JiraErrorBody {
+    #[serde(rename = "errorMessages", default)]
+    error_messages: Vec<String>,
+    #[serde(default)]
+    errors: HashMap<String, String>,
+}
+
+impl JiraClient {
+    /// Create a new Jira client with the given configuration.
+    pub fn new(config: ConfluenceConfig) -> JiraResult<Self> {
+        let _base_url = Url::parse(&config.base_url).map_err(|_| JiraError::Config {
+            message: format!("Invalid base URL: {}", config.base_url),
+        })?;
+        let headers = auth_headers(&config).map_err(|_| JiraError::Authentication {
+            message: "Failed to create authorization header".to_string(),
+        })?;
+        Ok(JiraClient {
+            client: Client::new(),
+            config,
+            headers,
+            field_registry: OnceCell::new(),
+        })
+    }
+
+    /// Fetch one page of issues matching a JQL query.
+    pub fn search_issues_page(
+        &self,
+        jql: &str,
+        start_at: usize,
+        max_results: usize,
+        options: &JiraSearchOptions,
+    ) -> JiraResult<JiraSearchResponse> {
+        let mut url = format!(
+            "{}/rest/api/3/search?jql={}&startAt={start_at}&maxResults={max_results}",
+            self.config.base_url,
+            urlencoding::encode(jql)
+        );
+        if !options.fields.is_empty() {
+            url.push_str(&format!(
+                "&fields={}",
+                urlencoding::encode(&options.fields.join(","))
+            ));
+        }
+        if !options.expand.is_empty() {
+            url.push_str(&format!(
+                "&expand={}",
+                urlencoding::encode(&options.expand.join(","))
+            ));
+        }
+
+        let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = error_message(response);
+            return Err(match status {
+                401 | 403 => JiraError::Authentication { message },
+                400 => JiraError::JqlQuery {
+                    query: jql.to_string(),
+                    message,
+                },
+                _ => JiraError::ApiError {
+                    status,
+                    message: format!("Failed to search issues: HTTP {status}: {message}"),
+                },
+            });
+        }
+
+        Ok(response.json()?)
+    }
+
+    /// Execute a JQL query and return every matching issue, up to `options.limit`.
+    pub fn search_issues(
+        &self,
+        jql: &str,
+        options: &JiraSearchOptions,
+    ) -> JiraResult<Vec<JiraIssue>> {
+        let mut issues = Vec::new();
+        loop {
+            let wanted = options
+                .limit
+                .map_or(MAX_SEARCH_PAGE_SIZE, |limit| limit - issues.len())
+                .min(MAX_SEARCH_PAGE_SIZE);
+            if wanted == 0 {
+                break;
+            }
+
+            let page = self.search_issues_page(jql, issues.len(), wanted, options)?;
+            let received = page.issues.len();
+            issues.extend(page.issues);
+            if received == 0 || issues.len() >= page.total {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+}
+
+/// Extract a readable message from a failed Jira response.
+pub(crate) fn error_message(response: Response) -> String {
+    let text = response
+        .text()
+        .unwrap_or_else(|_| "Unknown error".to_string());
+    let Ok(body) = serde_json::from_str::<JiraErrorBody>(&text) else {
+        return text;
+    };
+
+    let mut messages = body.error_messages;
+    let mut field_errors: Vec<String> = body
+        .errors
+        .into_iter()
+        .map(|(field, message)| format!("{field}: {message}"))
+        .collect();
+    field_errors.sort();
+    messages.extend(field_errors);
+    if messages.is_empty() {
+        text
+    } else {
+        messages.join("; ")
+    }
+}
@@ -1,17 +1,51 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
+pub mod adf;
+pub mod agile;
+pub mod attachments;
+pub mod comments;
 pub mod confluence;
+pub mod content;
 pub mod errors;
+pub mod fields;
+pub mod history;
+pub mod issue_comments;
+pub mod issue_links;
+pub mod issues;
+pub mod jira;
+pub mod markdown;
 pub mod models;
+pub mod projects;
+pub mod restrictions;
+pub mod spaces;
+pub mod transitions;
+pub mod worklogs;
 
+pub use adf::*;
+pub use agile::*;
+pub use attachments::*;
+pub use comments::*;
 pub use confluence::*;
+pub use content::*;
 pub use errors::*;
+pub use fields::*;
+pub use issue_comments::*;
+pub use issue_links::*;
+pub use issues::*;
+pub use jira::*;
+pub use markdown::*;
 pub use models::*;
+pub use projects::*;
+pub use restrictions::*;
+pub use spaces::*;
+pub use transitions::*;
+pub use worklogs::*;
 
 /// A page with additional metadata information about actions to take.
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct ConfluencePageTree {
-    /// The root page URL
+    /// The root page URL, or the page ID when built from a fetched page
     pub root_page: String,
 
     /// The current labels on the page.
@@ -39,6 +73,17 @@ pub enum PageLabelAction {
     List,
 }
 
+/// How a desired label set is reconciled with the labels already on a page.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelMode {
+    /// Add missing labels and keep any others
+    #[default]
+    Additive,
+    /// Add missing labels and remove any others
+    Exact,
+}
+
 impl ConfluencePageTree {
     /// Convenience constructor.
     pub fn new(root_page: impl Into<String>) -> Self {
@@ -90,22 +135,76 @@ impl ConfluencePageTree {
         });
     }
 
-    /// Stubbed: apply all recorded actions.
+    /// Build a tree for a fetched page, using its ID and current labels.
+    pub fn from_page(page: &ConfluencePage) -> Self {
+        ConfluencePageTree {
+            root_page: page.id.clone(),
+            current_page_labels: page
+                .metadata
+                .as_ref()
+                .and_then(|m| m.labels.as_ref())
+                .map(|labels| labels.results.iter().map(|l| l.name.clone()).collect())
+                .unwrap_or_default(),
+            tag_actions: Vec::new(),
+        }
+    }
+
+    /// Record the actions needed to reach a desired label set.
+    ///
+    /// Labels listed in `aliases` (alias -> canonical name) are updated to
+    /// their canonical name when that name is desired and not yet present.
+    pub fn plan_labels(
+        &mut self,
+        desired: &[String],
+        mode: LabelMode,
+        aliases: &HashMap<String, String>,
+    ) {
+        for current in self.current_page_labels.clone() {
+            if let Some(canonical) = aliases.get(&current) {
+                if desired.contains(canonical) && !self.current_page_labels.contains(canonical) {
+                    self.update_label(&current, canonical);
+                }
+            }
+        }
+
+        for label in desired {
+            if !self.current_page_labels.contains(label) {
+                self.add_label(label.clone());
+            }
+        }
+
+        if mode == LabelMode::Exact {
+            for current in self.current_page_labels.clone() {
+                if !desired.contains(&current) {
+                    self.delete_label(&current);
+                }
+            }
+        }
+    }
+
+    /// Apply all recorded actions to the page through the Confluence API.
     ///
-    /// This is a no-op placeholder that demonstrates the API surface for the
-    /// shared library. If `dry_run` is true, the function will not perform
-    /// destructive operations (still a no-op here) and will return Ok.
-    pub fn apply_actions(
-        &self,
-        dry_run: bool,
-    ) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    /// `root_page` must hold the page ID. If `dry_run` is true, nothing is
+    /// sent to Confluence.
+    pub fn apply_actions(&self, client: &ConfluenceClient, dry_run: bool) -> Result<()> {
         if dry_run {
-            // In a real implementation, we'd log what we'd do.
-            // Keep as a no-op for stubbing purposes.
             return Ok(());
         }
 
-        // Placeholder: pretend we applied actions successfully.
+        for action in &self.tag_actions {
+            match action {
+                PageLabelAction::Add { tag } => {
+                    client.add_page_labels(&self.root_page, &[tag.as_str()])?
+                }
+                PageLabelAction::Update { from, to } => {
+                    client.update_page_label(&self.root_page, from, to)?
+                }
+                PageLabelAction::Delete { tag } => {
+                    client.remove_page_labels(&self.root_page, &[tag.as_str()])?
+                }
+                PageLabelAction::List => {}
+            }
+        }
         Ok(())
     }
 }
@@ -0,0 +1,116 @@
+//! Conversion between Markdown and Confluence storage format.
+
+use pulldown_cmark::{html, CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+
+/// Render Markdown into Confluence storage format (XHTML).
+///
+/// Fenced code blocks are emitted as Confluence `code` macros so that syntax
+/// highlighting is preserved; everything else uses the plain XHTML output.
+pub fn markdown_to_storage(markdown: &str) -> String {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_FOOTNOTES;
+    let parser = Parser::new_ext(markdown, options);
+
+    let mut events = Vec::new();
+    let mut code_block: Option<(String, String)> = None;
+
+    for event in parser {
+        match event {
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let language = match kind {
+                    CodeBlockKind::Fenced(lang) => lang.to_string(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                code_block = Some((language, String::new()));
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                if let Some((language, code)) = code_block.take() {
+                    events.push(Event::Html(code_macro(&language, &code).into()));
+                }
+            }
+            Event::Text(text) if code_block.is_some() => {
+                if let Some((_, code)) = code_block.as_mut() {
+                    code.push_str(&text);
+                }
+            }
+            other => events.push(other),
+        }
+    }
+
+    let mut output = String::new();
+    html::push_html(&mut output, events.into_iter());
+    output
+}
+
+/// Render Confluence storage format (XHTML) as Markdown.
+///
+/// Confluence `code` macros are turned back into fenced code blocks; other
+/// macros are reduced to their text content.
+pub fn storage_to_markdown(storage: &str) -> String {
+    html2md::parse_html(&code_macros_to_html(storage))
+        .trim()
+        .to_string()
+}
+
+/// Replace Confluence code macros with plain `<pre><code>` blocks.
+fn code_macros_to_html(storage: &str) -> String {
+    const MACRO_START: &str = "<ac:structured-macro ac:name=\"code\"";
+    const MACRO_END: &str = "</ac:structured-macro>";
+
+    let mut out = String::with_capacity(storage.len());
+    let mut rest = storage;
+    while let Some(start) = rest.find(MACRO_START) {
+        out.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find(MACRO_END) else {
+            rest = &rest[start..];
+            break;
+        };
+        let body = &rest[start..start + end];
+        let language = between(
+            body,
+            "<ac:parameter ac:name=\"language\">",
+            "</ac:parameter>",
+        );
+        let code = between(body, "<![CDATA[", "]]></ac:plain-text-body>").unwrap_or_default();
+        let class = language
+            .map(|l| format!(" class=\"language-{l}\""))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "<pre><code{class}>{}</code></pre>",
+            escape_html(&code.replace("]]]]><![CDATA[>", "]]>"))
+        ));
+        rest = &rest[start + end + MACRO_END.len()..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Return the text between the first `open` and the following `close`.
+fn between<'a>(text: &'a str, open: &str, close: &str) -> Option<&'a str> {
+    let start = text.find(open)? + open.len();
+    let end = text[start..].find(close)?;
+    Some(&text[start..start + end])
+}
+
+/// Escape text for inclusion in HTML.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Build a Confluence code macro for a block of code.
+fn code_macro(language: &str, code: &str) -> String {
+    let mut out = String::from("<ac:structured-macro ac:name=\"code\">");
+    if !language.is_empty() {
+        out.push_str(&format!(
+            "<ac:parameter ac:name=\"language\">{language}</ac:parameter>"
+        ));
+    }
+    out.push_str("<ac:plain-text-body><![CDATA[");
+    out.push_str(&code.replace("]]>", "]]]]><![CDATA[>"));
+    out.push_str("]]></ac:plain-text-body></ac:structured-macro>\n");
+    out
+}
@@ -46,6 +46,8 @@ pub struct Project {
     pub key: String,
     /// Project/space description
     pub description: Option<String>,
-    /// Project type (e.g., "space", "project")
+    /// Project type (e.g., "space", "software", "service_desk")
     pub project_type: String,
+    /// Display name of the project lead, if any
+    pub lead: Option<String>,
 }
@@ -0,0 +1,127 @@
+//! Jira projects and permission checks.
+
+use crate::errors::{JiraError, JiraResult};
+use crate::jira::{error_message, JiraClient, JiraUser};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Represents a Jira project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraProject {
+    /// Project ID
+    pub id: String,
+    /// Project key
+    pub key: String,
+    /// Project name
+    pub name: String,
+    /// Project type ("software", "business" or "service_desk")
+    #[serde(rename = "projectTypeKey")]
+    pub project_type: Option<String>,
+    /// Project description (requires `description` expansion)
+    pub description: Option<String>,
+    /// Project lead (requires `lead` expansion)
+    pub lead: Option<JiraUser>,
+    /// Whether the project has been archived
+    #[serde(default)]
+    pub archived: bool,
+}
+
+/// One page of results from the project search endpoint.
+#[derive(Debug, Clone, Deserialize)]
+struct JiraProjectPage {
+    #[serde(default)]
+    values: Vec<JiraProject>,
+    #[serde(rename = "isLast", default)]
+    is_last: bool,
+    #[serde(rename = "nextPage")]
+    next_page: Option<String>,
+}
+
+/// A permission as reported by the `mypermissions` endpoint.
+#[derive(Debug, Clone, Deserialize)]
+struct JiraPermission {
+    #[serde(rename = "havePermission", default)]
+    have_permission: bool,
+}
+
+/// Response from the `mypermissions` endpoint.
+#[derive(Debug, Clone, Deserialize)]
+struct MyPermissionsResponse {
+    #[serde(default)]
+    permissions: HashMap<String, JiraPermission>,
+}
+
+impl JiraClient {
+    /// Get every project visible to the current user, with lead and description.
+    pub fn get_projects(&self) -> JiraResult<Vec<JiraProject>> {
+        let mut url = format!(
+            "{}/rest/api/3/project/search?expand=lead,description&maxResults=50",
+            self.config.base_url
+        );
+        let mut projects = Vec::new();
+
+        loop {
+            let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+            if !response.status().is_success() {
+                let status = response.status().as_u16();
+                let message = error_message(response);
+                return Err(JiraError::ApiError {
+                    status,
+                    message: format!("Failed to get projects: HTTP {status}: {message}"),
+                });
+            }
+
+            let page: JiraProjectPage = response.json()?;
+            projects.extend(page.values);
+
+            match page.next_page {
+                Some(next) if !page.is_last => url = next,
+                _ => break,
+            }
+        }
+
+        Ok(projects)
+    }
+
+    /// Check whether the current user holds a permission (e.g. "BROWSE_PROJECTS").
+    ///
+    /// Without a project this reports whether the permission is held in at
+    /// least one project.
+    pub fn has_permission(&self, permission: &str, project_key: Option<&str>) -> JiraResult<bool> {
+        let mut url = format!(
+            "{}/rest/api/3/mypermissions?permissions={}",
+            self.config.base_url,
+            urlencoding::encode(permission)
+        );
+        if let Some(key) = project_key {
+            url.push_str(&format!("&projectKey={}", urlencoding::encode(key)));
+        }
+
+        let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+        if response.status() == 404 {
+            return Err(JiraError::ProjectNotFound {
+                key: project_key.unwrap_or_default().to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = error_message(response);
+            return Err(match status {
+                401 => JiraError::Authentication { message },
+                _ => JiraError::ApiError {
+                    status,
+                    message: format!("Failed to check permissions: HTTP {status}: {message}"),
+                },
+            });
+        }
+
+        let permissions: MyPermissionsResponse = response.json()?;
+        Ok(permissions
+            .permissions
+            .get(permission)
+            .is_some_and(|p| p.have_permission))
+    }
+}
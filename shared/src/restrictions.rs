@@ -0,0 +1,255 @@
+//! Page restrictions: who may view (read) and edit (update) a page.
+
+use crate::confluence::ConfluenceClient;
+use crate::content::ContentUser;
+use crate::errors::{ConfluenceError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Operation a page restriction applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RestrictionOperation {
+    /// Viewing the page
+    Read,
+    /// Editing the page
+    Update,
+}
+
+/// A user or group a restriction grants access to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(tag = "type", content = "id", rename_all = "lowercase")]
+pub enum RestrictionSubject {
+    /// User identified by Atlassian account ID
+    User(String),
+    /// Group identified by name
+    Group(String),
+}
+
+/// Users and groups allowed to perform one operation on a page.
+///
+/// An empty set means the operation is not restricted on this page.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RestrictionSet {
+    /// Users allowed to perform the operation
+    pub users: Vec<ContentUser>,
+    /// Names of groups allowed to perform the operation
+    pub groups: Vec<String>,
+}
+
+/// Read and update restrictions of a page.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageRestrictions {
+    /// Who may view the page
+    pub read: RestrictionSet,
+    /// Who may edit the page
+    pub update: RestrictionSet,
+}
+
+/// Raw response of the restrictions-by-operation endpoint.
+#[derive(Debug, Clone, Deserialize)]
+struct RestrictionsByOperation {
+    read: Option<OperationRestrictions>,
+    update: Option<OperationRestrictions>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OperationRestrictions {
+    restrictions: RestrictionLists,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RestrictionLists {
+    user: Option<RestrictionResults<ContentUser>>,
+    group: Option<RestrictionResults<RestrictionGroup>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RestrictionResults<T> {
+    results: Vec<T>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RestrictionGroup {
+    name: String,
+}
+
+impl RestrictionOperation {
+    /// Name used by the REST API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RestrictionOperation::Read => "read",
+            RestrictionOperation::Update => "update",
+        }
+    }
+}
+
+impl std::fmt::Display for RestrictionSubject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RestrictionSubject::User(account_id) => write!(f, "user:{account_id}"),
+            RestrictionSubject::Group(name) => write!(f, "group:{name}"),
+        }
+    }
+}
+
+impl RestrictionSet {
+    /// Whether the operation is unrestricted.
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty() && self.groups.is_empty()
+    }
+
+    /// All subjects in the set, sorted.
+    pub fn subjects(&self) -> Vec<RestrictionSubject> {
+        let mut subjects: Vec<RestrictionSubject> = self
+            .users
+            .iter()
+            .filter_map(|u| u.account_id.clone().map(RestrictionSubject::User))
+            .chain(self.groups.iter().cloned().map(RestrictionSubject::Group))
+            .collect();
+        subjects.sort();
+        subjects
+    }
+}
+
+impl PageRestrictions {
+    /// Restrictions for one operation.
+    pub fn get(&self, operation: RestrictionOperation) -> &RestrictionSet {
+        match operation {
+            RestrictionOperation::Read => &self.read,
+            RestrictionOperation::Update => &self.update,
+        }
+    }
+}
+
+impl From<Option<OperationRestrictions>> for RestrictionSet {
+    fn from(raw: Option<OperationRestrictions>) -> Self {
+        let Some(raw) = raw else {
+            return RestrictionSet::default();
+        };
+        RestrictionSet {
+            users: raw.restrictions.user.map(|u| u.results).unwrap_or_default(),
+            groups: raw
+                .restrictions
+                .group
+                .map(|g| g.results.into_iter().map(|g| g.name).collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl ConfluenceClient {
+    /// Get the read and update restrictions set directly on a page.
+    pub fn get_page_restrictions(&self, page_id: &str) -> Result<PageRestrictions> {
+        let url = format!(
+            "{}/wiki/rest/api/content/{}/restriction/byOperation?expand=restrictions.user,restrictions.group",
+            self.config.base_url, page_id
+        );
+
+        let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+        if response.status() == 404 {
+            return Err(ConfluenceError::PageNotFound {
+                page_id: page_id.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!(
+                    "Failed to get restrictions for page {page_id}: HTTP {status}: {error_text}"
+                ),
+            });
+        }
+
+        let raw: RestrictionsByOperation = response.json()?;
+        Ok(PageRestrictions {
+            read: raw.read.into(),
+            update: raw.update.into(),
+        })
+    }
+
+    /// Grant a user or group permission to perform an operation on a page.
+    pub fn add_page_restriction(
+        &self,
+        page_id: &str,
+        operation: RestrictionOperation,
+        subject: &RestrictionSubject,
+    ) -> Result<()> {
+        let url = self.restriction_url(page_id, operation, subject);
+        let response = self.client.put(&url).headers(self.headers.clone()).send()?;
+        check_restriction_response(response, page_id, "add", subject)
+    }
+
+    /// Remove a user or group from the restrictions of an operation on a page.
+    pub fn remove_page_restriction(
+        &self,
+        page_id: &str,
+        operation: RestrictionOperation,
+        subject: &RestrictionSubject,
+    ) -> Result<()> {
+        let url = self.restriction_url(page_id, operation, subject);
+        let response = self
+            .client
+            .delete(&url)
+            .headers(self.headers.clone())
+            .send()?;
+        check_restriction_response(response, page_id, "remove", subject)
+    }
+
+    /// URL addressing a single subject's restriction on a page.
+    fn restriction_url(
+        &self,
+        page_id: &str,
+        operation: RestrictionOperation,
+        subject: &RestrictionSubject,
+    ) -> String {
+        let base = format!(
+            "{}/wiki/rest/api/content/{}/restriction/byOperation/{}",
+            self.config.base_url,
+            page_id,
+            operation.as_str()
+        );
+        match subject {
+            RestrictionSubject::User(account_id) => {
+                format!("{base}/user?accountId={}", urlencoding::encode(account_id))
+            }
+            RestrictionSubject::Group(name) => {
+                format!("{base}/group/{}", urlencoding::encode(name))
+            }
+        }
+    }
+}
+
+/// Map the response of a restriction change to a result.
+fn check_restriction_response(
+    response: reqwest::blocking::Response,
+    page_id: &str,
+    action: &str,
+    subject: &RestrictionSubject,
+) -> Result<()> {
+    if response.status() == 404 {
+        return Err(ConfluenceError::PageNotFound {
+            page_id: page_id.to_string(),
+        });
+    }
+
+    if !response.status().is_success() {
+        let status = response.status().as_u16();
+        let error_text = response
+            .text()
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(ConfluenceError::ApiError {
+            status,
+            message: format!(
+                "Failed to {action} restriction for {subject} on page {page_id}: HTTP {status}: {error_text}"
+            ),
+        });
+    }
+
+    Ok(())
+}
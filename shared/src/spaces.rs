@@ -0,0 +1,418 @@
+//! Space administration: lifecycle (create, update, archive, restore, delete)
+//! and permissions granted on a space.
+
+use crate::confluence::{ConfluenceClient, ConfluenceSpace, SpacesResponse};
+use crate::content::ContentUser;
+use crate::errors::{ConfluenceError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Kind of space to create.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpaceKind {
+    /// Space visible according to its permissions
+    Global,
+    /// Space visible only to its creator
+    Private,
+}
+
+/// Changes to apply to a space; fields left as `None` are unchanged.
+#[derive(Debug, Clone, Default)]
+pub struct SpaceUpdate {
+    /// New space name
+    pub name: Option<String>,
+    /// New plain-text description
+    pub description: Option<String>,
+    /// New status ("current" or "archived")
+    pub status: Option<String>,
+}
+
+/// Who a space permission is granted to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum PermissionSubject {
+    /// Anonymous (not logged in) visitors
+    Anonymous,
+    /// Logged-in users without a product licence
+    Unlicensed,
+    /// A single user
+    User {
+        /// Atlassian account ID
+        account_id: String,
+        /// Display name
+        display_name: Option<String>,
+    },
+    /// A group
+    Group {
+        /// Group name
+        name: String,
+    },
+}
+
+/// A single operation granted to a subject on a space.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct SpacePermission {
+    /// Who the permission is granted to
+    pub subject: PermissionSubject,
+    /// Operation, e.g. "read", "create" or "administer"
+    pub operation: String,
+    /// Target of the operation, e.g. "space", "page" or "comment"
+    pub target: String,
+}
+
+/// Space as returned with the `permissions` expansion.
+#[derive(Debug, Clone, Deserialize)]
+struct SpaceWithPermissions {
+    #[serde(default)]
+    permissions: Vec<RawSpacePermission>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawSpacePermission {
+    subjects: Option<RawPermissionSubjects>,
+    operation: RawPermissionOperation,
+    #[serde(default)]
+    anonymous_access: bool,
+    #[serde(default)]
+    unlicensed_access: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RawPermissionSubjects {
+    user: Option<RawResults<ContentUser>>,
+    group: Option<RawResults<RawGroup>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RawResults<T> {
+    results: Vec<T>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RawGroup {
+    name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPermissionOperation {
+    operation: String,
+    target_type: String,
+}
+
+impl PermissionSubject {
+    /// Whether the subject covers people outside of named users and groups.
+    pub fn is_public(&self) -> bool {
+        matches!(
+            self,
+            PermissionSubject::Anonymous | PermissionSubject::Unlicensed
+        )
+    }
+}
+
+impl std::fmt::Display for PermissionSubject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PermissionSubject::Anonymous => write!(f, "anonymous"),
+            PermissionSubject::Unlicensed => write!(f, "unlicensed"),
+            PermissionSubject::User {
+                account_id,
+                display_name: Some(name),
+            } => write!(f, "user:{name} ({account_id})"),
+            PermissionSubject::User { account_id, .. } => write!(f, "user:{account_id}"),
+            PermissionSubject::Group { name } => write!(f, "group:{name}"),
+        }
+    }
+}
+
+impl SpacePermission {
+    /// Combined operation and target, e.g. "create:page".
+    pub fn operation_key(&self) -> String {
+        format!("{}:{}", self.operation, self.target)
+    }
+}
+
+impl RawSpacePermission {
+    /// Expand one raw permission into a permission per subject.
+    fn into_permissions(self) -> Vec<SpacePermission> {
+        let mut subjects = Vec::new();
+        if self.anonymous_access {
+            subjects.push(PermissionSubject::Anonymous);
+        }
+        if self.unlicensed_access {
+            subjects.push(PermissionSubject::Unlicensed);
+        }
+        if let Some(raw) = self.subjects {
+            for user in raw.user.map(|u| u.results).unwrap_or_default() {
+                if let Some(account_id) = user.account_id {
+                    subjects.push(PermissionSubject::User {
+                        account_id,
+                        display_name: user.display_name,
+                    });
+                }
+            }
+            for group in raw.group.map(|g| g.results).unwrap_or_default() {
+                subjects.push(PermissionSubject::Group { name: group.name });
+            }
+        }
+
+        subjects
+            .into_iter()
+            .map(|subject| SpacePermission {
+                subject,
+                operation: self.operation.operation.clone(),
+                target: self.operation.target_type.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Plain-text description in the shape the space API expects.
+fn plain_description(description: &str) -> serde_json::Value {
+    serde_json::json!({ "plain": { "value": description, "representation": "plain" } })
+}
+
+impl ConfluenceClient {
+    /// Get a single space by key.
+    pub fn get_space(&self, space_key: &str) -> Result<ConfluenceSpace> {
+        let url = format!(
+            "{}/wiki/rest/api/space/{}?expand=description.plain",
+            self.config.base_url,
+            urlencoding::encode(space_key)
+        );
+
+        let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+        if response.status() == 404 {
+            return Err(ConfluenceError::SpaceNotFound {
+                space_key: space_key.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!("Failed to get space {space_key}: HTTP {status}: {error_text}"),
+            });
+        }
+
+        Ok(response.json()?)
+    }
+
+    /// Get all archived spaces.
+    pub fn get_archived_spaces(&self) -> Result<Vec<ConfluenceSpace>> {
+        let url = format!(
+            "{}/wiki/rest/api/space?status=archived&expand=description.plain&limit=1000",
+            self.config.base_url
+        );
+
+        let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!("Failed to get archived spaces: HTTP {status}: {error_text}"),
+            });
+        }
+
+        let spaces_response: SpacesResponse = response.json()?;
+        Ok(spaces_response.results)
+    }
+
+    /// Create a new space.
+    pub fn create_space(
+        &self,
+        key: &str,
+        name: &str,
+        description: Option<&str>,
+        kind: SpaceKind,
+    ) -> Result<ConfluenceSpace> {
+        let endpoint = match kind {
+            SpaceKind::Global => "space",
+            SpaceKind::Private => "space/_private",
+        };
+        let url = format!("{}/wiki/rest/api/{endpoint}", self.config.base_url);
+
+        let mut request_body = serde_json::json!({ "key": key, "name": name });
+        if let Some(description) = description {
+            request_body["description"] = plain_description(description);
+        }
+
+        let response = self
+            .client
+            .post(&url)
+            .headers(self.headers.clone())
+            .json(&request_body)
+            .send()?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!("Failed to create space {key}: HTTP {status}: {error_text}"),
+            });
+        }
+
+        Ok(response.json()?)
+    }
+
+    /// Update the name, description or status of a space.
+    pub fn update_space(&self, space_key: &str, update: &SpaceUpdate) -> Result<ConfluenceSpace> {
+        let url = format!(
+            "{}/wiki/rest/api/space/{}",
+            self.config.base_url,
+            urlencoding::encode(space_key)
+        );
+
+        let mut request_body = serde_json::json!({});
+        if let Some(name) = &update.name {
+            request_body["name"] = name.as_str().into();
+        }
+        if let Some(description) = &update.description {
+            request_body["description"] = plain_description(description);
+        }
+        if let Some(status) = &update.status {
+            request_body["status"] = status.as_str().into();
+        }
+
+        let response = self
+            .client
+            .put(&url)
+            .headers(self.headers.clone())
+            .json(&request_body)
+            .send()?;
+
+        if response.status() == 404 {
+            return Err(ConfluenceError::SpaceNotFound {
+                space_key: space_key.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!("Failed to update space {space_key}: HTTP {status}: {error_text}"),
+            });
+        }
+
+        Ok(response.json()?)
+    }
+
+    /// Archive a space, hiding it from search and the space directory.
+    pub fn archive_space(&self, space_key: &str) -> Result<ConfluenceSpace> {
+        self.update_space(
+            space_key,
+            &SpaceUpdate {
+                status: Some("archived".to_string()),
+                ..SpaceUpdate::default()
+            },
+        )
+    }
+
+    /// Restore an archived space.
+    pub fn restore_space(&self, space_key: &str) -> Result<ConfluenceSpace> {
+        self.update_space(
+            space_key,
+            &SpaceUpdate {
+                status: Some("current".to_string()),
+                ..SpaceUpdate::default()
+            },
+        )
+    }
+
+    /// Start deleting a space (moving it to the trash).
+    ///
+    /// Returns the ID of the long-running task performing the deletion.
+    pub fn delete_space(&self, space_key: &str) -> Result<String> {
+        let url = format!(
+            "{}/wiki/rest/api/space/{}",
+            self.config.base_url,
+            urlencoding::encode(space_key)
+        );
+
+        let response = self
+            .client
+            .delete(&url)
+            .headers(self.headers.clone())
+            .send()?;
+
+        if response.status() == 404 {
+            return Err(ConfluenceError::SpaceNotFound {
+                space_key: space_key.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!("Failed to delete space {space_key}: HTTP {status}: {error_text}"),
+            });
+        }
+
+        let task: serde_json::Value = response.json()?;
+        task["id"].as_str().map(|id| id.to_string()).ok_or_else(|| {
+            ConfluenceError::InvalidResponse {
+                message: format!("Delete of space {space_key} returned no task ID"),
+            }
+        })
+    }
+
+    /// Get all permissions granted on a space, sorted by subject and operation.
+    pub fn get_space_permissions(&self, space_key: &str) -> Result<Vec<SpacePermission>> {
+        let url = format!(
+            "{}/wiki/rest/api/space/{}?expand=permissions",
+            self.config.base_url,
+            urlencoding::encode(space_key)
+        );
+
+        let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+        if response.status() == 404 {
+            return Err(ConfluenceError::SpaceNotFound {
+                space_key: space_key.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ConfluenceError::ApiError {
+                status,
+                message: format!(
+                    "Failed to get permissions for space {space_key}: HTTP {status}: {error_text}"
+                ),
+            });
+        }
+
+        let space: SpaceWithPermissions = response.json()?;
+        let mut permissions: Vec<SpacePermission> = space
+            .permissions
+            .into_iter()
+            .flat_map(RawSpacePermission::into_permissions)
+            .collect();
+        permissions.sort();
+        permissions.dedup();
+        Ok(permissions)
+    }
+}
@@ -0,0 +1,117 @@
+//! Jira workflow transitions.
+
+use crate::errors::{JiraError, JiraResult};
+use crate::fields::JiraFieldMeta;
+use crate::jira::{error_message, JiraClient, JiraStatus};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// A transition available on an issue in its current status.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraTransition {
+    /// Transition ID
+    pub id: String,
+    /// Transition name (e.g., "Resolve Issue")
+    pub name: String,
+    /// Status the issue moves to
+    pub to: JiraStatus,
+    /// Whether the transition shows a screen
+    #[serde(rename = "hasScreen", default)]
+    pub has_screen: bool,
+    /// Fields on the transition screen, keyed by field ID
+    #[serde(default)]
+    pub fields: HashMap<String, JiraFieldMeta>,
+}
+
+/// Response from the transitions endpoint.
+#[derive(Debug, Deserialize)]
+struct TransitionsResponse {
+    #[serde(default)]
+    transitions: Vec<JiraTransition>,
+}
+
+impl JiraTransition {
+    /// Required screen fields that have neither a default nor a value in `fields`.
+    pub fn missing_fields(&self, fields: &Map<String, Value>) -> Vec<&str> {
+        let mut missing: Vec<&str> = self
+            .fields
+            .iter()
+            .filter(|(id, f)| f.required && !f.has_default_value && !fields.contains_key(*id))
+            .map(|(_, f)| f.name.as_str())
+            .collect();
+        missing.sort();
+        missing
+    }
+}
+
+impl JiraClient {
+    /// Get the transitions available on an issue, with their screen fields.
+    pub fn get_transitions(&self, key: &str) -> JiraResult<Vec<JiraTransition>> {
+        let url = format!(
+            "{}/rest/api/3/issue/{}/transitions?expand=transitions.fields",
+            self.config.base_url, key
+        );
+
+        let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+        if response.status() == 404 {
+            return Err(JiraError::IssueNotFound {
+                key: key.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = error_message(response);
+            return Err(JiraError::ApiError {
+                status,
+                message: format!("Failed to get transitions of {key}: HTTP {status}: {message}"),
+            });
+        }
+
+        let transitions: TransitionsResponse = response.json()?;
+        Ok(transitions.transitions)
+    }
+
+    /// Perform a transition, setting screen fields such as the resolution.
+    pub fn transition_issue(
+        &self,
+        key: &str,
+        transition_id: &str,
+        fields: &Map<String, Value>,
+    ) -> JiraResult<()> {
+        let url = format!(
+            "{}/rest/api/3/issue/{}/transitions",
+            self.config.base_url, key
+        );
+        let mut body = serde_json::json!({ "transition": { "id": transition_id } });
+        if !fields.is_empty() {
+            body["fields"] = Value::Object(fields.clone());
+        }
+
+        let response = self
+            .client
+            .post(&url)
+            .headers(self.headers.clone())
+            .json(&body)
+            .send()?;
+
+        if response.status() == 404 {
+            return Err(JiraError::IssueNotFound {
+                key: key.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = error_message(response);
+            return Err(JiraError::ApiError {
+                status,
+                message: format!("Failed to transition {key}: HTTP {status}: {message}"),
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+//! Jira issue worklogs.
+
+use crate::errors::{JiraError, JiraResult};
+use crate::jira::{error_message, JiraClient, JiraUser};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Page size used when listing worklogs.
+const WORKLOG_PAGE_SIZE: usize = 1000;
+
+/// Time logged against a Jira issue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraWorklog {
+    /// Worklog ID
+    pub id: String,
+    /// ID of the issue the time was logged on
+    #[serde(rename = "issueId")]
+    pub issue_id: Option<String>,
+    /// User who logged the time
+    pub author: Option<JiraUser>,
+    /// Comment in Atlassian Document Format
+    pub comment: Option<Value>,
+    /// When the work started (e.g., "2024-03-01T09:00:00.000+0000")
+    pub started: Option<String>,
+    /// Time spent as entered (e.g., "1h 30m")
+    #[serde(rename = "timeSpent")]
+    pub time_spent: Option<String>,
+    /// Time spent in seconds
+    #[serde(rename = "timeSpentSeconds", default)]
+    pub time_spent_seconds: u64,
+}
+
+/// Time to log against an issue.
+#[derive(Debug, Clone, Serialize)]
+pub struct JiraNewWorklog {
+    /// Time spent in Jira duration format (e.g., "1h 30m" or "2d")
+    #[serde(rename = "timeSpent")]
+    pub time_spent: String,
+    /// When the work started; Jira uses the current time when absent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started: Option<String>,
+    /// Comment in Atlassian Document Format
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<Value>,
+}
+
+/// A page of worklogs.
+#[derive(Debug, Deserialize)]
+struct WorklogPage {
+    #[serde(default)]
+    total: usize,
+    #[serde(default)]
+    worklogs: Vec<JiraWorklog>,
+}
+
+impl JiraClient {
+    /// Get the worklogs of an issue, optionally only those started at or after
+    /// `started_after` (Unix time in milliseconds).
+    pub fn get_worklogs(
+        &self,
+        key: &str,
+        started_after: Option<i64>,
+    ) -> JiraResult<Vec<JiraWorklog>> {
+        let mut worklogs = Vec::new();
+        loop {
+            let mut url = format!(
+                "{}/rest/api/3/issue/{}/worklog?startAt={}&maxResults={WORKLOG_PAGE_SIZE}",
+                self.config.base_url,
+                key,
+                worklogs.len()
+            );
+            if let Some(started_after) = started_after {
+                url.push_str(&format!("&startedAfter={started_after}"));
+            }
+
+            let response = self.client.get(&url).headers(self.headers.clone()).send()?;
+
+            if response.status() == 404 {
+                return Err(JiraError::IssueNotFound {
+                    key: key.to_string(),
+                });
+            }
+
+            if !response.status().is_success() {
+                let status = response.status().as_u16();
+                let message = error_message(response);
+                return Err(JiraError::ApiError {
+                    status,
+                    message: format!("Failed to get worklogs of {key}: HTTP {status}: {message}"),
+                });
+            }
+
+            let page: WorklogPage = response.json()?;
+            let received = page.worklogs.len();
+            worklogs.extend(page.worklogs);
+            if received == 0 || worklogs.len() >= page.total {
+                break;
+            }
+        }
+        Ok(worklogs)
+    }
+
+    /// Log time against an issue.
+    pub fn add_worklog(&self, key: &str, worklog: &JiraNewWorklog) -> JiraResult<JiraWorklog> {
+        let url = format!("{}/rest/api/3/issue/{}/worklog", self.config.base_url, key);
+
+        let response = self
+            .client
+            .post(&url)
+            .headers(self.headers.clone())
+            .json(worklog)
+            .send()?;
+
+        if response.status() == 404 {
+            return Err(JiraError::IssueNotFound {
+                key: key.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = error_message(response);
+            return Err(JiraError::ApiError {
+                status,
+                message: format!("Failed to log work on {key}: HTTP {status}: {message}"),
+            });
+        }
+
+        Ok(response.json()?)
+    }
+}
@@ -2,9 +2,10 @@
 
 use crate::{
     command::{AvailableCommand, CommandExecutor, CommandInput},
-    create_confluence_client,
+    create_confluence_client, create_jira_client,
     domain_loader::DomainLoader,
     event_handler::EventHandler,
+    issue_view::IssueView,
     models::{AtlassianDomain, NavigationContext, TreeItem, TreeItemWithMetadata},
     screens::Screen,
     search::SearchManager,
@@ -13,7 +14,7 @@ use crate::{
     ui::Ui,
 };
 use crossterm::event::{self, Event};
-use nix_rust_template::ConfluenceClient;
+use nix_rust_template::{ConfluenceClient, JiraClient};
 use ratatui::{backend::Backend, Terminal};
 use std::{error::Error, time::Duration};
 
@@ -25,12 +26,16 @@ pub struct App {
     pub current_screen: Screen,
     /// Confluence client for API operations
     pub confluence_client: ConfluenceClient,
+    /// Jira client for API operations
+    pub jira_client: JiraClient,
     /// UI state handler
     pub ui: Ui,
     /// Tree navigation manager
     pub tree_navigation: TreeNavigationManager,
     /// Search manager
     pub search_manager: SearchManager,
+    /// Issues of the selected Jira project
+    pub issue_view: IssueView,
     /// Available domain loaded from environment
     pub domain: Option<AtlassianDomain>,
     /// Command executor for running CLI commands
@@ -45,6 +50,7 @@ impl App {
     /// Create a new App instance
     pub fn new() -> Result<Self, Box<dyn Error>> {
         let confluence_client = create_confluence_client()?;
+        let jira_client = create_jira_client()?;
         let ui = Ui::new();
 
         let tree_navigation = TreeNavigationManager::new();
@@ -55,9 +61,11 @@ impl App {
             should_quit: false,
             current_screen: Screen::TreeNavigation,
             confluence_client,
+            jira_client,
             ui,
             tree_navigation,
             search_manager,
+            issue_view: IssueView::new(),
             domain: None,
             command_executor,
             command_input: CommandInput::new(),
@@ -110,6 +118,7 @@ impl App {
     fn cleanup_resources(&mut self) {
         self.tree_navigation.cleanup();
         self.search_manager.cleanup();
+        self.issue_view.cleanup();
         self.domain = None;
     }
 
@@ -128,7 +137,11 @@ impl App {
         &mut self,
         confluence_client: ConfluenceClient,
     ) -> Result<(), Box<dyn Error>> {
-        let domain_loader = DomainLoader::new(confluence_client);
+        let include_archived = std::env::var("ATUI_INCLUDE_ARCHIVED_SPACES")
+            .is_ok_and(|v| matches!(v.as_str(), "1" | "true" | "yes"));
+        let domain_loader = DomainLoader::new(confluence_client)
+            .with_jira_client(create_jira_client()?)
+            .with_archived_spaces(include_archived);
         let domain = domain_loader.load_domain_data()?;
 
         self.tree_navigation.build_tree_data(domain.clone());
@@ -137,6 +150,24 @@ impl App {
         Ok(())
     }
 
+    /// Load the issues of the selected Jira project and show them
+    pub fn open_issue_view(&mut self) {
+        let Some(project) = self.tree_navigation.navigation_context.project.clone() else {
+            return;
+        };
+        match self.issue_view.load(&self.jira_client, &project.key) {
+            Ok(()) => {
+                self.ui.set_status(format!(
+                    "{} issues in {}",
+                    self.issue_view.issues.len(),
+                    project.key
+                ));
+                self.switch_screen(Screen::IssueView);
+            }
+            Err(e) => self.ui.set_status(format!("Failed to load issues: {e}")),
+        }
+    }
+
     /// Get available commands for the current context
     pub fn get_available_commands(&self) -> Vec<AvailableCommand> {
         self.command_executor.get_available_commands()
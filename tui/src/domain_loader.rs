@@ -1,18 +1,36 @@
 //! Domain loading and Atlassian service discovery
 
 use crate::models::{AtlassianDomain, AtlassianProduct, ProductType, Project};
-use nix_rust_template::ConfluenceClient;
+use nix_rust_template::{ConfluenceClient, ConfluenceSpace, JiraClient, JiraProject};
 use std::error::Error;
 
 /// Domain loader for discovering and loading Atlassian services
 pub struct DomainLoader {
     confluence_client: ConfluenceClient,
+    jira_client: Option<JiraClient>,
+    include_archived_spaces: bool,
 }
 
 impl DomainLoader {
     /// Create a new domain loader with the given Confluence client
     pub fn new(confluence_client: ConfluenceClient) -> Self {
-        Self { confluence_client }
+        Self {
+            confluence_client,
+            jira_client: None,
+            include_archived_spaces: false,
+        }
+    }
+
+    /// Discover Jira projects with the given client
+    pub fn with_jira_client(mut self, jira_client: JiraClient) -> Self {
+        self.jira_client = Some(jira_client);
+        self
+    }
+
+    /// Also list archived spaces in the Confluence tree
+    pub fn with_archived_spaces(mut self, include: bool) -> Self {
+        self.include_archived_spaces = include;
+        self
     }
 
     /// Load domain data from environment variables and discover products/projects
@@ -42,36 +60,33 @@ impl DomainLoader {
         let confluence_product = self.discover_confluence_product()?;
         domain.products.push(confluence_product);
 
-        // Add placeholder for other products
-        domain.products.push(AtlassianProduct {
-            product_type: ProductType::Jira,
-            name: "Jira (coming soon)".to_string(),
-            projects: Vec::new(),
-            available: false,
-        });
-
-        domain.products.push(AtlassianProduct {
-            product_type: ProductType::Jsm,
-            name: "Jira Service Management (coming soon)".to_string(),
-            projects: Vec::new(),
-            available: false,
-        });
+        // Discover Jira and Jira Service Management projects
+        if let Some(jira_client) = &self.jira_client {
+            domain
+                .products
+                .extend(Self::discover_jira_products(jira_client));
+        }
 
         Ok(domain)
     }
 
     /// Discover Confluence product and its spaces
     fn discover_confluence_product(&self) -> Result<AtlassianProduct, Box<dyn Error>> {
-        match self.confluence_client.get_spaces() {
+        match self.load_spaces() {
             Ok(spaces) => {
                 let confluence_projects: Vec<Project> = spaces
                     .into_iter()
                     .map(|space| Project {
                         id: space.id,
-                        name: space.name,
+                        name: if space.status == "archived" {
+                            format!("{} (archived)", space.name)
+                        } else {
+                            space.name
+                        },
                         key: space.key,
                         description: space.description.and_then(|d| d.plain).map(|p| p.value),
                         project_type: "space".to_string(),
+                        lead: None,
                     })
                     .collect();
 
@@ -94,4 +109,78 @@ impl DomainLoader {
             }
         }
     }
+
+    /// Discover Jira projects, split into Jira and Jira Service Management
+    fn discover_jira_products(jira_client: &JiraClient) -> Vec<AtlassianProduct> {
+        let projects = jira_client
+            .has_permission("BROWSE_PROJECTS", None)
+            .and_then(|browse| {
+                if browse {
+                    jira_client.get_projects().map(Some)
+                } else {
+                    Ok(None)
+                }
+            });
+
+        match projects {
+            Ok(Some(projects)) => {
+                let (service_desks, projects): (Vec<JiraProject>, Vec<JiraProject>) = projects
+                    .into_iter()
+                    .filter(|p| !p.archived)
+                    .partition(|p| p.project_type.as_deref() == Some("service_desk"));
+                vec![
+                    AtlassianProduct {
+                        product_type: ProductType::Jira,
+                        name: "Jira".to_string(),
+                        projects: projects.into_iter().map(jira_project).collect(),
+                        available: true,
+                    },
+                    AtlassianProduct {
+                        product_type: ProductType::Jsm,
+                        name: "Jira Service Management".to_string(),
+                        projects: service_desks.into_iter().map(jira_project).collect(),
+                        available: true,
+                    },
+                ]
+            }
+            Ok(None) => vec![AtlassianProduct {
+                product_type: ProductType::Jira,
+                name: "Jira (no access)".to_string(),
+                projects: Vec::new(),
+                available: false,
+            }],
+            Err(e) => {
+                eprintln!("Jira API error: {e:?}");
+                vec![AtlassianProduct {
+                    product_type: ProductType::Jira,
+                    name: format!("Jira (Error: {e})"),
+                    projects: Vec::new(),
+                    available: false,
+                }]
+            }
+        }
+    }
+
+    /// Load current spaces, followed by archived ones when enabled
+    fn load_spaces(&self) -> nix_rust_template::Result<Vec<ConfluenceSpace>> {
+        let mut spaces = self.confluence_client.get_spaces()?;
+        if self.include_archived_spaces {
+            spaces.extend(self.confluence_client.get_archived_spaces()?);
+        }
+        Ok(spaces)
+    }
+}
+
+/// Map a Jira project onto a tree project
+fn jira_project(project: JiraProject) -> Project {
+    Project {
+        id: project.id,
+        name: project.name,
+        key: project.key,
+        description: project.description.filter(|d| !d.is_empty()),
+        project_type: project
+            .project_type
+            .unwrap_or_else(|| "project".to_string()),
+        lead: project.lead.and_then(|l| l.display_name),
+    }
 }
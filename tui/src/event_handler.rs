@@ -42,6 +42,9 @@ impl EventHandler {
                     Screen::CommandExecution => {
                         Self::handle_command_execution_input(app, code)?;
                     }
+                    Screen::IssueView => {
+                        Self::handle_issue_view_input(app, code);
+                    }
                     Screen::MainMenu => {
                         Self::handle_main_menu_input(app, code);
                     }
@@ -89,11 +92,12 @@ impl EventHandler {
             KeyCode::Left => {
                 app.tree_navigation.collapse_current_node();
             }
-            KeyCode::Char('c') => {
+            KeyCode::Char('c') if app.tree_navigation.navigation_context.is_complete() => {
                 // Switch to command execution for ctag
-                if app.tree_navigation.navigation_context.is_complete() {
-                    app.switch_screen(Screen::CommandExecution);
-                }
+                app.switch_screen(Screen::CommandExecution);
+            }
+            KeyCode::Char('i') if app.tree_navigation.navigation_context.is_jira_project() => {
+                app.open_issue_view();
             }
             KeyCode::Char('/') => {
                 // Enter search mode
@@ -174,21 +178,43 @@ impl EventHandler {
     /// Handle mouse events
     fn handle_mouse_event(app: &mut App, kind: MouseEventKind) -> Result<(), Box<dyn Error>> {
         match kind {
-            MouseEventKind::ScrollUp => {
-                if app.current_screen == Screen::TreeNavigation && !app.is_search_mode() {
-                    app.tree_navigation.move_selection_up();
-                }
+            MouseEventKind::ScrollUp
+                if app.current_screen == Screen::TreeNavigation && !app.is_search_mode() =>
+            {
+                app.tree_navigation.move_selection_up();
             }
-            MouseEventKind::ScrollDown => {
-                if app.current_screen == Screen::TreeNavigation && !app.is_search_mode() {
-                    app.tree_navigation.move_selection_down();
-                }
+            MouseEventKind::ScrollDown
+                if app.current_screen == Screen::TreeNavigation && !app.is_search_mode() =>
+            {
+                app.tree_navigation.move_selection_down();
             }
             _ => {}
         }
         Ok(())
     }
 
+    /// Handle issue view input
+    fn handle_issue_view_input(app: &mut App, code: KeyCode) {
+        match code {
+            KeyCode::Backspace | KeyCode::Esc => {
+                app.switch_screen(Screen::TreeNavigation);
+            }
+            KeyCode::Up => {
+                app.issue_view.move_selection_up();
+            }
+            KeyCode::Down => {
+                app.issue_view.move_selection_down();
+            }
+            KeyCode::PageUp => {
+                app.issue_view.page_up();
+            }
+            KeyCode::PageDown => {
+                app.issue_view.page_down();
+            }
+            _ => {}
+        }
+    }
+
     /// Handle main menu input
     fn handle_main_menu_input(app: &mut App, code: KeyCode) {
         match code {
@@ -293,19 +319,16 @@ impl EventHandler {
                     }
                 }
             }
-            KeyCode::Up => {
+            KeyCode::Up
                 if app.command_input.mode == CommandInputMode::SelectingCommand
-                    && app.command_selection > 0
-                {
-                    app.command_selection -= 1;
-                }
-            }
-            KeyCode::Down => {
-                if app.command_input.mode == CommandInputMode::SelectingCommand {
-                    let available_commands = app.command_executor.get_available_commands();
-                    if app.command_selection < available_commands.len().saturating_sub(1) {
-                        app.command_selection += 1;
-                    }
+                    && app.command_selection > 0 =>
+            {
+                app.command_selection -= 1;
+            }
+            KeyCode::Down if app.command_input.mode == CommandInputMode::SelectingCommand => {
+                let available_commands = app.command_executor.get_available_commands();
+                if app.command_selection < available_commands.len().saturating_sub(1) {
+                    app.command_selection += 1;
                 }
             }
             KeyCode::Left => {
@@ -356,7 +379,8 @@ impl EventHandler {
 
     /// Execute the currently selected command
     fn execute_selected_command(app: &mut App) -> Result<(), Box<dyn Error>> {
-        if let Some(AvailableCommand::Ctag { operation, .. }) = &app.command_input.selected_command {
+        if let Some(AvailableCommand::Ctag { operation, .. }) = &app.command_input.selected_command
+        {
             // Parse additional arguments from command input
             let args: Vec<String> = if app.command_input.text.trim().is_empty() {
                 Vec::new()
@@ -0,0 +1,152 @@
+//! Issue view for Jira projects
+
+use nix_rust_template::{adf_to_markdown, JiraClient, JiraIssue, JiraSearchOptions};
+use std::error::Error;
+
+/// Number of issues loaded for a project
+const ISSUE_LIMIT: usize = 100;
+
+/// Issues of the selected Jira project and the one being read
+pub struct IssueView {
+    /// Key of the project the issues belong to
+    pub project_key: String,
+    /// Most recently updated issues of the project
+    pub issues: Vec<JiraIssue>,
+    /// Index of the selected issue
+    pub selection: usize,
+    /// Lines scrolled past in the selected issue's details
+    pub scroll: u16,
+}
+
+impl Default for IssueView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IssueView {
+    /// Create an empty issue view
+    pub fn new() -> Self {
+        Self {
+            project_key: String::new(),
+            issues: Vec::new(),
+            selection: 0,
+            scroll: 0,
+        }
+    }
+
+    /// Load the most recently updated issues of a project
+    pub fn load(&mut self, client: &JiraClient, project_key: &str) -> Result<(), Box<dyn Error>> {
+        let options = JiraSearchOptions {
+            fields: [
+                "summary",
+                "status",
+                "issuetype",
+                "priority",
+                "assignee",
+                "labels",
+                "description",
+                "updated",
+            ]
+            .map(str::to_string)
+            .to_vec(),
+            limit: Some(ISSUE_LIMIT),
+            ..JiraSearchOptions::default()
+        };
+        let jql = format!("project = \"{project_key}\" ORDER BY updated DESC");
+        self.issues = client.search_issues(&jql, &options)?;
+        self.project_key = project_key.to_string();
+        self.selection = 0;
+        self.scroll = 0;
+        Ok(())
+    }
+
+    /// The selected issue
+    pub fn selected(&self) -> Option<&JiraIssue> {
+        self.issues.get(self.selection)
+    }
+
+    /// Select the previous issue
+    pub fn move_selection_up(&mut self) {
+        if self.selection > 0 {
+            self.selection -= 1;
+            self.scroll = 0;
+        }
+    }
+
+    /// Select the next issue
+    pub fn move_selection_down(&mut self) {
+        if self.selection < self.issues.len().saturating_sub(1) {
+            self.selection += 1;
+            self.scroll = 0;
+        }
+    }
+
+    /// Scroll the details up by a page (10 lines)
+    pub fn page_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(10);
+    }
+
+    /// Scroll the details down by a page (10 lines)
+    pub fn page_down(&mut self) {
+        let last = self.detail_lines().len().saturating_sub(1);
+        self.scroll = (self.scroll + 10).min(last as u16);
+    }
+
+    /// Details of the selected issue: its fields, then its description as Markdown
+    pub fn detail_lines(&self) -> Vec<String> {
+        let Some(issue) = self.selected() else {
+            return Vec::new();
+        };
+        let fields = &issue.fields;
+
+        let mut lines = vec![
+            format!(
+                "{}  {}",
+                issue.key,
+                fields.summary.as_deref().unwrap_or_default()
+            ),
+            String::new(),
+        ];
+        let mut field = |name: &str, value: Option<String>| {
+            if let Some(value) = value.filter(|v| !v.is_empty()) {
+                lines.push(format!("{name:<10} {value}"));
+            }
+        };
+        field("Status:", fields.status.as_ref().map(|s| s.name.clone()));
+        field("Type:", fields.issue_type.as_ref().map(|t| t.name.clone()));
+        field(
+            "Priority:",
+            fields.priority.as_ref().map(|p| p.name.clone()),
+        );
+        field(
+            "Assignee:",
+            fields
+                .assignee
+                .as_ref()
+                .and_then(|a| a.display_name.clone()),
+        );
+        field("Labels:", fields.labels.as_ref().map(|l| l.join(", ")));
+        field("Updated:", fields.updated.clone());
+
+        let description = fields
+            .description
+            .as_ref()
+            .map(adf_to_markdown)
+            .unwrap_or_default();
+        lines.push(String::new());
+        if description.is_empty() {
+            lines.push("No description.".to_string());
+        } else {
+            lines.extend(description.lines().map(str::to_string));
+        }
+        lines
+    }
+
+    /// Clear loaded issues to prevent memory leaks
+    pub fn cleanup(&mut self) {
+        self.issues.clear();
+        self.selection = 0;
+        self.scroll = 0;
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! This crate provides an interactive TUI for working with Atlassian products.
 
-use nix_rust_template::{ConfluenceClient, ConfluenceConfig};
+use nix_rust_template::{ConfluenceClient, ConfluenceConfig, JiraClient};
 use std::error::Error;
 
 pub mod app;
@@ -10,6 +10,7 @@ pub mod command;
 pub mod domain_loader;
 pub mod event;
 pub mod event_handler;
+pub mod issue_view;
 pub mod models;
 pub mod screens;
 pub mod search;
@@ -26,8 +27,8 @@ pub fn run_tui() -> Result<(), Box<dyn Error>> {
     app.run()
 }
 
-/// Create a Confluence client using environment variables
-pub fn create_confluence_client() -> Result<ConfluenceClient, Box<dyn Error>> {
+/// Read the Atlassian site configuration from environment variables
+pub fn load_atlassian_config() -> Result<ConfluenceConfig, Box<dyn Error>> {
     dotenv::dotenv().ok(); // Load .env file, ignore if not found
 
     let base_url =
@@ -37,11 +38,19 @@ pub fn create_confluence_client() -> Result<ConfluenceClient, Box<dyn Error>> {
     let api_token = std::env::var("ATLASSIAN_API_TOKEN")
         .map_err(|_| "ATLASSIAN_API_TOKEN environment variable not set")?;
 
-    let config = ConfluenceConfig {
+    Ok(ConfluenceConfig {
         base_url,
         username,
         api_token,
-    };
+    })
+}
+
+/// Create a Confluence client using environment variables
+pub fn create_confluence_client() -> Result<ConfluenceClient, Box<dyn Error>> {
+    ConfluenceClient::new(load_atlassian_config()?).map_err(|e| e.into())
+}
 
-    ConfluenceClient::new(config).map_err(|e| e.into())
+/// Create a Jira client using the same environment variables as Confluence
+pub fn create_jira_client() -> Result<JiraClient, Box<dyn Error>> {
+    JiraClient::new(load_atlassian_config()?).map_err(|e| e.into())
 }
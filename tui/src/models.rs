@@ -117,6 +117,15 @@ impl NavigationContext {
         }
     }
 
+    /// Check if the selected project is a Jira or Jira Service Management project
+    pub fn is_jira_project(&self) -> bool {
+        self.project.is_some()
+            && self
+                .product
+                .as_ref()
+                .is_some_and(|p| matches!(p.product_type, ProductType::Jira | ProductType::Jsm))
+    }
+
     /// Get CQL context for command execution
     pub fn cql_context(&self) -> Option<String> {
         if !self.is_complete() {
@@ -21,6 +21,8 @@ pub enum Screen {
     Help,
     /// Command execution screen for running ctag commands
     CommandExecution,
+    /// Issue list and details for a Jira project
+    IssueView,
 }
 
 impl Screen {
@@ -33,6 +35,7 @@ impl Screen {
         match self {
             Screen::TreeNavigation => self.handle_tree_navigation_keys(app, key_code),
             Screen::CommandExecution => self.handle_command_execution_keys(app, key_code),
+            Screen::IssueView => self.handle_issue_view_keys(app, key_code),
             Screen::MainMenu => self.handle_main_menu_keys(app, key_code),
             Screen::CqlBuilder => self.handle_cql_builder_keys(app, key_code),
             Screen::PageBrowser => self.handle_page_browser_keys(app, key_code),
@@ -174,11 +177,30 @@ impl Screen {
             KeyCode::Left => {
                 // TODO: Collapse node
             }
-            KeyCode::Char('c') => {
+            KeyCode::Char('c') if app.get_navigation_context().is_complete() => {
                 // Switch to command execution for ctag
-                if app.get_navigation_context().is_complete() {
-                    app.switch_screen(Screen::CommandExecution);
-                }
+                app.switch_screen(Screen::CommandExecution);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Handle keys for issue view screen
+    fn handle_issue_view_keys(
+        &mut self,
+        app: &mut App,
+        key_code: KeyCode,
+    ) -> Result<(), Box<dyn Error>> {
+        match key_code {
+            KeyCode::Backspace | KeyCode::Esc => {
+                app.switch_screen(Screen::TreeNavigation);
+            }
+            KeyCode::Up => {
+                app.issue_view.move_selection_up();
+            }
+            KeyCode::Down => {
+                app.issue_view.move_selection_down();
             }
             _ => {}
         }
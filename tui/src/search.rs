@@ -116,7 +116,7 @@ impl SearchManager {
             .collect();
 
         // Sort by score (highest first) for fzf-like ranking
-        matches.sort_by(|a, b| b.3.cmp(&a.3));
+        matches.sort_by_key(|b| std::cmp::Reverse(b.3));
 
         self.filtered_tree_items = Some(matches);
         0 // Reset selection to top
@@ -43,6 +43,7 @@ impl Ui {
         match app.current_screen {
             Screen::TreeNavigation => self.draw_tree_navigation(f, chunks[0], app),
             Screen::CommandExecution => self.draw_command_execution(f, chunks[0], app),
+            Screen::IssueView => self.draw_issue_view(f, chunks[0], app),
             Screen::MainMenu => self.draw_main_menu(f, chunks[0]),
             Screen::CqlBuilder => self.draw_cql_builder(f, chunks[0]),
             Screen::PageBrowser => self.draw_page_browser(f, chunks[0]),
@@ -72,6 +73,9 @@ impl Ui {
                 }
             }
             Screen::CommandExecution => "Enter: Execute | Esc: Back | q: Quit",
+            Screen::IssueView => {
+                "↑↓: Select issue | PgUp/PgDn: Scroll details | Esc: Back | q: Quit"
+            }
             Screen::MainMenu => {
                 "1: CQL Builder | 2: Page Browser | 3: Label Manager | h: Help | q: Quit"
             }
@@ -456,6 +460,7 @@ impl Ui {
             "Actions:".to_string(),
             "• Press Enter to select/expand".to_string(),
             "• Press 'c' for commands when project selected".to_string(),
+            "• Press 'i' for issues when a Jira project is selected".to_string(),
             "• Press '/' to search".to_string(),
             "• Use arrow keys to navigate".to_string(),
             "".to_string(),
@@ -479,6 +484,100 @@ impl Ui {
         f.render_widget(context_panel, chunks[1]);
     }
 
+    /// Draw the issue view screen: issue list on the left, details on the right
+    fn draw_issue_view(&self, f: &mut Frame, area: Rect, app: &App) {
+        let view = &app.issue_view;
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(40), // Issue list
+                Constraint::Percentage(60), // Issue details
+            ])
+            .split(area);
+
+        let issue_items: Vec<ListItem> = view
+            .issues
+            .iter()
+            .map(|issue| {
+                let status = issue
+                    .fields
+                    .status
+                    .as_ref()
+                    .map(|s| s.name.as_str())
+                    .unwrap_or_default();
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{:<10} ", issue.key),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    Span::raw(issue.fields.summary.clone().unwrap_or_default()),
+                    Span::styled(format!("  [{status}]"), Style::default().fg(Color::Gray)),
+                ]))
+            })
+            .collect();
+
+        let issues = List::new(issue_items)
+            .block(
+                Block::default()
+                    .title(format!(
+                        "Issues in {} ({})",
+                        view.project_key,
+                        view.issues.len()
+                    ))
+                    .borders(Borders::ALL)
+                    .style(Style::default().fg(Color::White)),
+            )
+            .style(Style::default().fg(Color::White))
+            .highlight_style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::REVERSED),
+            )
+            .highlight_symbol("▶ ");
+
+        let mut list_state = ratatui::widgets::ListState::default();
+        list_state.select(Some(view.selection));
+        f.render_stateful_widget(issues, chunks[0], &mut list_state);
+
+        // Description lines are Markdown; style headings, quotes and code
+        let mut in_code = false;
+        let detail_lines: Vec<Line> = view
+            .detail_lines()
+            .into_iter()
+            .map(|line| {
+                let fence = line.trim_start().starts_with("```");
+                let style = if fence || in_code {
+                    Style::default().fg(Color::Yellow)
+                } else if line.starts_with('#') {
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD)
+                } else if line.starts_with('>') {
+                    Style::default()
+                        .fg(Color::Gray)
+                        .add_modifier(Modifier::ITALIC)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                if fence {
+                    in_code = !in_code;
+                }
+                Line::styled(line, style)
+            })
+            .collect();
+
+        let details = Paragraph::new(detail_lines)
+            .block(
+                Block::default()
+                    .title("Details")
+                    .borders(Borders::ALL)
+                    .style(Style::default().fg(Color::Cyan)),
+            )
+            .wrap(Wrap { trim: false })
+            .scroll((view.scroll, 0));
+        f.render_widget(details, chunks[1]);
+    }
+
     /// Draw the command execution screen
     fn draw_command_execution(&self, f: &mut Frame, area: Rect, app: &App) {
         use crate::command::{AvailableCommand, CommandInputMode};
@@ -684,7 +783,7 @@ impl Ui {
         text: &str,
         match_positions: &[usize],
         _query: &str,
-    ) -> Vec<Span> {
+    ) -> Vec<Span<'_>> {
         if match_positions.is_empty() {
             return vec![Span::raw(text.to_string())];
         }